@@ -1,7 +1,28 @@
 mod strength;
 mod representativeness;
+mod trend;
+mod sweep;
+mod plot;
+mod report;
+mod table;
+#[cfg(test)]
+mod test_util;
 
 fn main() {
-    strength::simulate();
-    representativeness::compute();
+    let args: Vec<String> = std::env::args().collect();
+    match args.get(1).map(String::as_str) {
+        None => {
+            strength::simulate();
+            representativeness::compute();
+            trend::analyze();
+            sweep::sweep();
+            plot::plot();
+        }
+        Some("report") => report::report(args.get(2).map(String::as_str).unwrap_or("report.html")),
+        Some(command) => {
+            eprintln!("Unknown command '{}'.", command);
+            eprintln!("Usage: {} [report [<file.html>]]", args[0]);
+            std::process::exit(1);
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,365 @@
+use std::fmt::Write;
+use std::fs;
+use rand_distr::num_traits::ToPrimitive;
+use crate::representativeness::failure_probability;
+use crate::strength::concentration::lorenz_curve;
+use crate::strength::simulation::Simulation;
+use crate::strength::stake_distribution::*;
+
+const WIDTH: f64 = 720.0;
+const HEIGHT: f64 = 440.0;
+const MARGIN_LEFT: f64 = 80.0;
+const MARGIN_RIGHT: f64 = 220.0;
+const MARGIN_TOP: f64 = 40.0;
+const MARGIN_BOTTOM: f64 = 60.0;
+const COLORS: [&str; 8] = ["#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd", "#8c564b", "#e377c2", "#7f7f7f"];
+
+// A sequence of (x, y) points.
+type Points = Vec<(f64, f64)>;
+
+// A named sequence of points.
+pub struct Series {
+    name: String,
+    points: Points,
+}
+
+// Plot renders series of points as an SVG chart, either as lines or (for histograms) as bars.
+// With a logarithmic y axis, points with y <= 0 are left out (and interrupt the line).
+// The SVG is written by hand, so that no external library or service is needed.
+pub struct Plot {
+    title: String,
+    x_label: String,
+    y_label: String,
+    log_y: bool,
+    // If set, each point is drawn as a bar of this width (in x units) centered at x.
+    bar_width: Option<f64>,
+    series: Vec<Series>,
+}
+
+impl Plot {
+    pub fn new(title: &str, x_label: &str, y_label: &str) -> Self {
+        Self {
+            title: title.to_owned(),
+            x_label: x_label.to_owned(),
+            y_label: y_label.to_owned(),
+            log_y: false,
+            bar_width: None,
+            series: vec![],
+        }
+    }
+
+    // Makes the y axis logarithmic.
+    pub fn with_log_y(mut self) -> Self {
+        self.log_y = true;
+        self
+    }
+
+    pub fn add_series(&mut self, name: &str, points: Points) {
+        self.series.push(Series { name: name.to_owned(), points });
+    }
+
+    // Creates a histogram of the given values, with the given number of equally wide bins
+    // between the smallest and the largest value. The y axis shows the number of values per bin.
+    // Without values, the histogram is empty.
+    pub fn histogram(title: &str, x_label: &str, values: &[f64], bins: usize) -> Self {
+        if bins == 0 {
+            panic!("A histogram needs at least one bin");
+        }
+        let mut plot = Self::new(title, x_label, "count");
+        if values.is_empty() {
+            plot.add_series("0 values", vec![]);
+            return plot;
+        }
+        let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        // Avoid zero-width bins if all values are equal.
+        let bin_width = if max > min { (max - min) / bins as f64 } else { 1.0 };
+        let mut counts = vec![0; bins];
+        for value in values {
+            let bin = (((value - min) / bin_width) as usize).min(bins - 1);
+            counts[bin] += 1;
+        }
+
+        plot.bar_width = Some(bin_width);
+        plot.add_series(
+            &format!("{} values", values.len()),
+            counts.into_iter().enumerate()
+                .map(|(bin, count)| (min + (bin as f64 + 0.5) * bin_width, count as f64))
+                .collect(),
+        );
+        plot
+    }
+
+    // Returns the range of the x axis.
+    fn x_range(&self) -> (f64, f64) {
+        let half_bar = self.bar_width.unwrap_or(0.0) / 2.0;
+        let xs = self.series.iter().flat_map(|s| s.points.iter().map(|p| p.0));
+        if xs.clone().next().is_none() {
+            return (0.0, 1.0);
+        }
+        let min = xs.clone().fold(f64::INFINITY, f64::min) - half_bar;
+        let max = xs.fold(f64::NEG_INFINITY, f64::max) + half_bar;
+        if max > min { (min, max) } else { (min - 1.0, min + 1.0) }
+    }
+
+    // Returns the range of the y axis, in log10 units for a logarithmic axis.
+    // A linear axis always starts at 0 (or lower for negative values).
+    fn y_range(&self) -> (f64, f64) {
+        let ys: Vec<f64> = self.series.iter()
+            .flat_map(|s| s.points.iter().map(|p| p.1))
+            .filter(|&y| !self.log_y || y > 0.0)
+            .collect();
+        if ys.is_empty() {
+            return (0.0, 1.0);
+        }
+        let min = ys.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = ys.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        if self.log_y {
+            let (min, max) = (min.log10().floor(), max.log10().ceil());
+            if max > min { (min, max) } else { (min, min + 1.0) }
+        } else {
+            let (min, max) = (min.min(0.0), max.max(0.0));
+            if max > min { (min, max * 1.05) } else { (0.0, 1.0) }
+        }
+    }
+
+    fn y_value(&self, y: f64) -> Option<f64> {
+        if !self.log_y {
+            Some(y)
+        } else if y > 0.0 {
+            Some(y.log10())
+        } else {
+            None
+        }
+    }
+
+    pub fn to_svg(&self) -> String {
+        let (x_min, x_max) = self.x_range();
+        let (y_min, y_max) = self.y_range();
+        let plot_width = WIDTH - MARGIN_LEFT - MARGIN_RIGHT;
+        let plot_height = HEIGHT - MARGIN_TOP - MARGIN_BOTTOM;
+        let px = |x: f64| MARGIN_LEFT + (x - x_min) / (x_max - x_min) * plot_width;
+        let py = |y: f64| MARGIN_TOP + (y_max - y) / (y_max - y_min) * plot_height;
+
+        let mut svg = String::new();
+        writeln!(svg, r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" font-family="sans-serif" font-size="12">"#, WIDTH, HEIGHT).unwrap();
+        writeln!(svg, r#"<rect width="100%" height="100%" fill="white"/>"#).unwrap();
+        writeln!(svg, r#"<text x="{}" y="24" text-anchor="middle" font-size="16">{}</text>"#, MARGIN_LEFT + plot_width / 2.0, escape(&self.title)).unwrap();
+
+        // Grid lines and tick labels.
+        for x in ticks(x_min, x_max) {
+            writeln!(svg, r##"<line x1="{0:.1}" y1="{1:.1}" x2="{0:.1}" y2="{2:.1}" stroke="#ddd"/>"##, px(x), MARGIN_TOP, MARGIN_TOP + plot_height).unwrap();
+            writeln!(svg, r#"<text x="{:.1}" y="{:.1}" text-anchor="middle">{}</text>"#, px(x), MARGIN_TOP + plot_height + 16.0, format_tick(x)).unwrap();
+        }
+        let y_ticks = if self.log_y {
+            // One tick per power of 10, thinned out to at most about 10 ticks.
+            let step = ((y_max - y_min) / 10.0).ceil().max(1.0);
+            let first = (y_min / step).ceil() * step;
+            (0..).map(|i| first + i as f64 * step).take_while(|&y| y <= y_max).collect()
+        } else {
+            ticks(y_min, y_max)
+        };
+        for y in y_ticks {
+            let label = if self.log_y { format!("1e{}", y) } else { format_tick(y) };
+            writeln!(svg, r##"<line x1="{0:.1}" y1="{1:.1}" x2="{2:.1}" y2="{1:.1}" stroke="#ddd"/>"##, MARGIN_LEFT, py(y), MARGIN_LEFT + plot_width).unwrap();
+            writeln!(svg, r#"<text x="{:.1}" y="{:.1}" text-anchor="end">{}</text>"#, MARGIN_LEFT - 6.0, py(y) + 4.0, label).unwrap();
+        }
+        writeln!(svg, r#"<rect x="{}" y="{}" width="{}" height="{}" fill="none" stroke="black"/>"#, MARGIN_LEFT, MARGIN_TOP, plot_width, plot_height).unwrap();
+        writeln!(svg, r#"<text x="{}" y="{}" text-anchor="middle">{}</text>"#, MARGIN_LEFT + plot_width / 2.0, HEIGHT - 16.0, escape(&self.x_label)).unwrap();
+        writeln!(svg, r#"<text x="20" y="{0}" text-anchor="middle" transform="rotate(-90 20 {0})">{1}</text>"#, MARGIN_TOP + plot_height / 2.0, escape(&self.y_label)).unwrap();
+
+        for (i, series) in self.series.iter().enumerate() {
+            let color = COLORS[i % COLORS.len()];
+            match self.bar_width {
+                Some(bar_width) => {
+                    for &(x, y) in &series.points {
+                        let top = py(y.max(y_min));
+                        writeln!(svg, r#"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" fill="{}" stroke="white"/>"#,
+                                 px(x - bar_width / 2.0), top, px(x + bar_width / 2.0) - px(x - bar_width / 2.0), py(y_min.max(0.0)) - top, color).unwrap();
+                    }
+                }
+                None => {
+                    // Split the line where points cannot be shown (y <= 0 on a logarithmic axis).
+                    let mut segment = vec![];
+                    for &(x, y) in series.points.iter().chain([(f64::NAN, f64::NAN)].iter()) {
+                        match self.y_value(y).filter(|y| !y.is_nan()) {
+                            Some(y) => segment.push(format!("{:.1},{:.1}", px(x), py(y))),
+                            None => {
+                                if !segment.is_empty() {
+                                    writeln!(svg, r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="2"/>"#, segment.join(" "), color).unwrap();
+                                }
+                                segment.clear();
+                            }
+                        }
+                    }
+                }
+            }
+            // Legend entry.
+            let legend_y = MARGIN_TOP + 10.0 + 20.0 * i as f64;
+            writeln!(svg, r#"<rect x="{:.1}" y="{:.1}" width="14" height="10" fill="{}"/>"#, WIDTH - MARGIN_RIGHT + 14.0, legend_y - 9.0, color).unwrap();
+            writeln!(svg, r#"<text x="{:.1}" y="{:.1}">{}</text>"#, WIDTH - MARGIN_RIGHT + 34.0, legend_y, escape(&series.name)).unwrap();
+        }
+        svg.push_str("</svg>\n");
+
+        svg
+    }
+
+    pub fn save(&self, file_name: &str) {
+        fs::write(file_name, self.to_svg()).unwrap_or_else(|e| panic!("Cannot write '{}': {}", file_name, e));
+    }
+}
+
+// Returns evenly spaced tick positions between min and max, using a step of 1, 2 or 5 times a power
+// of 10, such that there are about 5 ticks.
+fn ticks(min: f64, max: f64) -> Vec<f64> {
+    let raw_step = (max - min) / 5.0;
+    let magnitude = 10f64.powf(raw_step.log10().floor());
+    let step = [1.0, 2.0, 5.0, 10.0].into_iter()
+        .map(|m| m * magnitude)
+        .find(|&s| s >= raw_step)
+        .unwrap();
+    let first = (min / step).ceil() as i64;
+    let last = (max / step).floor() as i64;
+    (first..=last).map(|i| i as f64 * step).collect()
+}
+
+fn format_tick(value: f64) -> String {
+    // Round away floating point noise such as 0.30000000000000004.
+    let rounded = (value * 1e9).round() / 1e9;
+    format!("{}", rounded)
+}
+
+// Escapes text for use in SVG and HTML documents.
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+// Returns a plot of the exact probability (see failure_probability()) of a sample containing a
+// fraction of at least fault_threshold faulty seats, for each of the given sample sizes.
+pub fn failure_probability_plot(fault_threshold: f64, threshold_name: &str, sample_sizes: &[u32]) -> Plot {
+    let mut plot = Plot::new(
+        &format!("Probability of at least {} faulty seats", threshold_name),
+        "sample size",
+        "failure probability",
+    ).with_log_y();
+    for (f_num, f_denom) in [(1, 5), (1, 4), (1, 3)] {
+        let points = sample_sizes.iter()
+            .map(|&n| (n as f64, failure_probability(n, f_num, f_denom, fault_threshold).to_f64().unwrap()))
+            .collect();
+        plot.add_series(&format!("f = {}/{}", f_num, f_denom), points);
+    }
+    plot
+}
+
+// Returns a plot of the Lorenz curves (see lorenz_curve()) of the given named distributions.
+pub fn lorenz_plot(distributions: &[(&str, &dyn StakeDistribution)]) -> Plot {
+    let mut plot = Plot::new("Lorenz curve", "fraction of nodes (least stake first)", "fraction of stake");
+    for &(name, distribution) in distributions {
+        plot.add_series(name, lorenz_curve(distribution));
+    }
+    plot
+}
+
+// Adds the average strong and weak quorum stakes of samples from the distribution of each of the
+// given sizes to the plot.
+pub fn add_quorum_stake_series<D: StakeDistribution>(plot: &mut Plot, name: &str, distribution: &D, sample_sizes: &[usize], iterations: usize) {
+    let mut weak = vec![];
+    let mut strong = vec![];
+    for &n in sample_sizes {
+        let mut simulation = Simulation::new(distribution);
+        simulation.run(n, iterations);
+        weak.push((n as f64, simulation.weak_quorum_stake().0));
+        strong.push((n as f64, simulation.strong_quorum_stake().0));
+    }
+    plot.add_series(&format!("{}, strong", name), strong);
+    plot.add_series(&format!("{}, weak", name), weak);
+}
+
+pub fn plot() {
+    let output_directory = "output";
+    let num_nodes = 296;
+    let exp_distribution_p = 0.1;
+    let actual_distribution_file = "node-stakes-september-2025";
+    let failure_sample_sizes: Vec<u32> = (10..=300).step_by(10).collect();
+    let strength_sample_sizes: Vec<usize> = (10..=300).step_by(20).collect();
+    let strength_iterations = 1000;
+    let histogram_sample_size = 111;
+    let histogram_bins = 40;
+
+    println!();
+    println!("================================================================================");
+    println!("PLOTS");
+    println!("================================================================================");
+    println!();
+
+    fs::create_dir_all(output_directory).unwrap();
+    let uniform_distribution = UniformStakeDistribution::new(num_nodes);
+    let exponential_distribution = ExponentialStakeDistribution::new(num_nodes, exp_distribution_p);
+    let actual_distribution = CustomStakeDistribution::from_file(actual_distribution_file);
+    let uniform_name = format!("uniform ({} nodes)", num_nodes);
+    let exponential_name = format!("exponential (p = {})", exp_distribution_p);
+    let mut files = vec![];
+
+    for (name, fault_threshold, threshold_name) in [("safety", 2.0/3.0, "2/3"), ("liveness", 1.0/3.0, "1/3")] {
+        let plot = failure_probability_plot(fault_threshold, threshold_name, &failure_sample_sizes);
+        files.push((format!("{}/failure-probability-{}.svg", output_directory, name), plot));
+    }
+
+    let mut plot = Plot::new("Average quorum stake", "sample size", "stake (fraction of total)");
+    add_quorum_stake_series(&mut plot, &uniform_name, &uniform_distribution, &strength_sample_sizes, strength_iterations);
+    add_quorum_stake_series(&mut plot, &exponential_name, &exponential_distribution, &strength_sample_sizes, strength_iterations);
+    add_quorum_stake_series(&mut plot, actual_distribution_file, &actual_distribution, &strength_sample_sizes, strength_iterations);
+    files.push((format!("{}/quorum-stake.svg", output_directory), plot));
+
+    let plot = lorenz_plot(&[
+        (&uniform_name, &uniform_distribution),
+        (&exponential_name, &exponential_distribution),
+        (actual_distribution_file, &actual_distribution),
+    ]);
+    files.push((format!("{}/lorenz-curve.svg", output_directory), plot));
+
+    let mut simulation = Simulation::new(&actual_distribution);
+    simulation.run(histogram_sample_size, strength_iterations);
+    for (name, stakes) in [("strong", simulation.strong_quorum_stakes()), ("weak", simulation.weak_quorum_stakes())] {
+        let plot = Plot::histogram(
+            &format!("{} quorum stake, n = {} ({})", name, histogram_sample_size, actual_distribution_file),
+            "stake (fraction of total)",
+            stakes,
+            histogram_bins,
+        );
+        files.push((format!("{}/quorum-stake-histogram-{}.svg", output_directory, name), plot));
+    }
+
+    for (file_name, plot) in &files {
+        plot.save(file_name);
+        println!("Wrote '{}'.", file_name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn svg_output() {
+        let mut plot = Plot::new("a < b", "x", "y").with_log_y();
+        plot.add_series("line", vec![(1.0, 0.1), (2.0, 0.0), (3.0, 0.01), (4.0, 0.001)]);
+        let svg = plot.to_svg();
+        assert!(svg.starts_with("<svg") && svg.ends_with("</svg>\n"));
+        assert!(svg.contains("a &lt; b"));
+        // The point with y = 0 splits the line on the logarithmic axis.
+        assert_eq!(svg.matches("<polyline").count(), 2);
+
+        let histogram = Plot::histogram("h", "x", &[0.0, 0.1, 0.1, 1.0], 2);
+        assert_eq!(histogram.series[0].points, vec![(0.25, 3.0), (0.75, 1.0)]);
+        let empty = Plot::histogram("h", "x", &[], 2);
+        assert!(empty.series[0].points.is_empty());
+        assert!(!empty.to_svg().contains("NaN"));
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one bin")]
+    fn histogram_without_bins() {
+        Plot::histogram("h", "x", &[1.0], 0);
+    }
+}
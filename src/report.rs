@@ -0,0 +1,158 @@
+use std::fmt::Write;
+use std::fs;
+use rand_distr::num_traits::ToPrimitive;
+use crate::plot::*;
+use crate::representativeness::{failure_probability, max_faulty_fraction, simulate_prob};
+use crate::strength::concentration::*;
+use crate::strength::simulation::Simulation;
+use crate::strength::stake_distribution::*;
+use crate::table::Table;
+
+const STYLE: &str = "body { font-family: sans-serif; max-width: 60em; margin: 2em auto; padding: 0 1em; }
+table { border-collapse: collapse; margin: 1em 0; }
+th, td { border: 1px solid #ccc; padding: 0.3em 0.6em; text-align: right; }
+th { background: #f4f4f4; }
+td.left, th.left { text-align: left; }
+svg { display: block; margin: 1em 0; }";
+
+// Returns the row of the strength table for one distribution.
+fn strength_row<D: StakeDistribution>(name: &str, distribution: &D, sample_size: usize, iterations: usize) -> Vec<String> {
+    let mut simulation = Simulation::new(distribution);
+    simulation.run(sample_size, iterations);
+    let format = |(avg, std_dev): (f64, f64)| format!("{:.4} ({:.4})", avg, std_dev);
+    vec![
+        name.to_owned(),
+        format(simulation.weak_quorum_stake()),
+        format(simulation.strong_quorum_stake()),
+        format(simulation.total_stakes()),
+    ]
+}
+
+// Runs the default scenario and writes a single self-contained HTML file (with the charts embedded
+// as inline SVG) containing its parameters, the stake distribution statistics, the sample strength
+// and the sample representativeness.
+pub fn report(file_name: &str) {
+    let num_nodes = 296;
+    let sample_size = 111;
+    let exp_distribution_p = 0.1;
+    let actual_distribution_file = "node-stakes-september-2025";
+    let iterations = 1000;
+    let sim_iterations = 100000;
+    let largest_validators = 10;
+    let sample_sizes = [90, 111, 141];
+    let faulty_fractions = [(1, 5), (1, 3)];
+    let failure_sample_sizes: Vec<u32> = (10..=300).step_by(10).collect();
+    let strength_sample_sizes: Vec<usize> = (10..=300).step_by(20).collect();
+    let histogram_bins = 40;
+    let max_fraction_precision_bits = 32;
+
+    let uniform_distribution = UniformStakeDistribution::new(num_nodes);
+    let exponential_distribution = ExponentialStakeDistribution::new(num_nodes, exp_distribution_p);
+    let actual_distribution = CustomStakeDistribution::from_file(actual_distribution_file);
+    let uniform_name = format!("uniform ({} nodes)", num_nodes);
+    let exponential_name = format!("exponential (p = {})", exp_distribution_p);
+
+    let mut html = String::new();
+    writeln!(html, "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">").unwrap();
+    writeln!(html, "<title>Validator sampling report</title>\n<style>\n{}\n</style>\n</head>\n<body>", STYLE).unwrap();
+    writeln!(html, "<h1>Validator sampling report</h1>").unwrap();
+
+    writeln!(html, "<h2>Parameters</h2>").unwrap();
+    let mut table = Table::new(&["parameter", "value"]).with_left_aligned(0);
+    table.add_row(vec!["stake distribution file".to_owned(), actual_distribution_file.to_owned()]);
+    table.add_row(vec!["sample size".to_owned(), sample_size.to_string()]);
+    table.add_row(vec!["nodes of the synthetic distributions".to_owned(), num_nodes.to_string()]);
+    table.add_row(vec!["exponential distribution parameter p".to_owned(), exp_distribution_p.to_string()]);
+    table.add_row(vec!["strength simulation iterations".to_owned(), iterations.to_string()]);
+    table.add_row(vec!["failure simulation iterations".to_owned(), sim_iterations.to_string()]);
+    html.push_str(&table.to_html());
+
+    writeln!(html, "<h2>Stake distribution</h2>").unwrap();
+    let mut table = Table::new(&["statistic", "value"]).with_left_aligned(0);
+    table.add_row(vec!["nodes".to_owned(), actual_distribution.num_nodes().to_string()]);
+    table.add_row(vec!["total stake".to_owned(), actual_distribution.total_stake().to_string()]);
+    table.add_row(vec!["Gini coefficient".to_owned(), format!("{:.4}", gini_coefficient(&actual_distribution))]);
+    table.add_row(vec!["Herfindahl-Hirschman index".to_owned(), format!("{:.4}", herfindahl_hirschman_index(&actual_distribution))]);
+    table.add_row(vec!["Nakamoto coefficient (1/3)".to_owned(), nakamoto_coefficient(&actual_distribution, 1.0/3.0).to_string()]);
+    table.add_row(vec!["Nakamoto coefficient (2/3)".to_owned(), nakamoto_coefficient(&actual_distribution, 2.0/3.0).to_string()]);
+    html.push_str(&table.to_html());
+    let mut nodes: Vec<usize> = (0..actual_distribution.num_nodes()).collect();
+    nodes.sort_unstable_by(|&a, &b| actual_distribution.stake_of(b).total_cmp(&actual_distribution.stake_of(a)));
+    let mut table = Table::new(&["validator", "stake"]).with_left_aligned(0);
+    for n in nodes.into_iter().take(largest_validators) {
+        table.add_row(vec![actual_distribution.node_name(n), format!("{:.4}", actual_distribution.stake_of(n))]);
+    }
+    writeln!(html, "<h3>Largest validators</h3>").unwrap();
+    html.push_str(&table.to_html());
+    html.push_str(&lorenz_plot(&[
+        (&uniform_name, &uniform_distribution),
+        (&exponential_name, &exponential_distribution),
+        (actual_distribution_file, &actual_distribution),
+    ]).to_svg());
+
+    writeln!(html, "<h2>Sample strength</h2>").unwrap();
+    writeln!(html, "<p>Stake backing the weakest weak (1/3) and strong (2/3) quorums of a sample of size {} and the \
+                    total stake of the sample, as a fraction of the total stake: average (standard deviation) over {} \
+                    samples.</p>", sample_size, iterations).unwrap();
+    let mut table = Table::new(&["distribution", "weak quorum stake", "strong quorum stake", "total stake"]).with_left_aligned(0);
+    table.add_row(strength_row(&uniform_name, &uniform_distribution, sample_size, iterations));
+    table.add_row(strength_row(&exponential_name, &exponential_distribution, sample_size, iterations));
+    table.add_row(strength_row(actual_distribution_file, &actual_distribution, sample_size, iterations));
+    html.push_str(&table.to_html());
+    let mut plot = Plot::new("Average quorum stake", "sample size", "stake (fraction of total)");
+    add_quorum_stake_series(&mut plot, &uniform_name, &uniform_distribution, &strength_sample_sizes, iterations);
+    add_quorum_stake_series(&mut plot, &exponential_name, &exponential_distribution, &strength_sample_sizes, iterations);
+    add_quorum_stake_series(&mut plot, actual_distribution_file, &actual_distribution, &strength_sample_sizes, iterations);
+    html.push_str(&plot.to_svg());
+    let mut simulation = Simulation::new(&actual_distribution);
+    simulation.run(sample_size, iterations);
+    html.push_str(&Plot::histogram(
+        &format!("Strong quorum stake, n = {} ({})", sample_size, actual_distribution_file),
+        "stake (fraction of total)",
+        simulation.strong_quorum_stakes(),
+        histogram_bins,
+    ).to_svg());
+
+    writeln!(html, "<h2>Sample representativeness</h2>").unwrap();
+    writeln!(html, "<p>Probability of a sample of size n containing at least 2/3 (not safe) or 1/3 (not live) \
+                    faulty seats, if a fraction f of the stake is faulty: exact (Calc) and simulated with {} \
+                    iterations (Sim).</p>", sim_iterations).unwrap();
+    let mut table = Table::new(&["n", "f", "not safe (Calc)", "not safe (Sim)", "not live (Calc)", "not live (Sim)"]);
+    for n in sample_sizes {
+        for (f_num, f_denom) in faulty_fractions {
+            let f = f_num as f64 / f_denom as f64;
+            table.add_row(vec![
+                n.to_string(),
+                format!("{}/{}", f_num, f_denom),
+                format!("{:.2e}", failure_probability(n, f_num, f_denom, 2.0/3.0).to_f64().unwrap()),
+                format!("{:.2e}", simulate_prob(n as u64, f, 2.0/3.0, sim_iterations)),
+                format!("{:.2e}", failure_probability(n, f_num, f_denom, 1.0/3.0).to_f64().unwrap()),
+                format!("{:.2e}", simulate_prob(n as u64, f, 1.0/3.0, sim_iterations)),
+            ]);
+        }
+    }
+    html.push_str(&table.to_html());
+    writeln!(html, "<p>Largest fraction f of faulty stake for which the probability of a sample of size n not being \
+                    safe / live stays below the target (exact up to {} binary digits).</p>", max_fraction_precision_bits).unwrap();
+    let mut table = Table::new(&["n", "condition", "target probability", "maximum f", "maximum f (exact)"]).with_left_aligned(1);
+    for n in sample_sizes {
+        for (fault_threshold, condition, max_prob) in [(2.0/3.0, "safe", 1.9e-13), (2.0/3.0, "safe", 1.9e-16), (1.0/3.0, "live", 1e-3)] {
+            let max_f = max_faulty_fraction(n, fault_threshold, max_prob, max_fraction_precision_bits);
+            table.add_row(vec![
+                n.to_string(),
+                condition.to_owned(),
+                format!("{:.1e}", max_prob),
+                max_f.as_ref().map_or("-".to_owned(), |f| format!("{:.6}", f.to_f64().unwrap())),
+                max_f.map_or("-".to_owned(), |f| f.to_string()),
+            ]);
+        }
+    }
+    html.push_str(&table.to_html());
+    html.push_str(&failure_probability_plot(2.0/3.0, "2/3", &failure_sample_sizes).to_svg());
+    html.push_str(&failure_probability_plot(1.0/3.0, "1/3", &failure_sample_sizes).to_svg());
+
+    writeln!(html, "</body>\n</html>").unwrap();
+    fs::write(file_name, html).unwrap_or_else(|e| panic!("Cannot write '{}': {}", file_name, e));
+    println!("Wrote report to '{}'.", file_name);
+}
+
@@ -0,0 +1,107 @@
+use std::fs;
+use rand::Rng;
+use crate::representativeness::poisson_binomial_cdf;
+use crate::strength::stake_distribution::StakeDistribution;
+use crate::strength::validator_sample::ValidatorSample;
+
+// Availability holds, for each node, the probability of the node being online (its uptime).
+// Nodes are online or offline independently of each other.
+pub struct Availability {
+    uptimes: Vec<f64>,
+}
+
+impl Availability {
+    // Loads the uptimes from a file containing one uptime percentage per line (e.g. "99.5" or
+    // "99.5%"), where the k-th line is the uptime of the k-th node (numbering starting from zero).
+    pub fn from_file(file_name: &str) -> Self {
+        let file_content = fs::read_to_string(file_name).unwrap();
+        let uptimes = file_content.lines()
+            .map(|line| {
+                let percentage: f64 = line.trim().trim_end_matches('%').parse()
+                    .unwrap_or_else(|_| panic!("Invalid uptime value: '{}'", line));
+                if !(0.0..=100.0).contains(&percentage) {
+                    panic!("Invalid uptime value: '{}'", line);
+                }
+                percentage / 100.0
+            })
+            .collect();
+
+        Self { uptimes }
+    }
+
+    // All nodes have the same uptime (as a probability between 0 and 1).
+    pub fn uniform(num_nodes: usize, uptime: f64) -> Self {
+        Self { uptimes: vec![uptime; num_nodes] }
+    }
+
+    // Returns the probability of the node being online. Panics if the uptime of the node is unknown.
+    pub fn uptime(&self, node: usize) -> f64 {
+        match self.uptimes.get(node) {
+            Some(&uptime) => uptime,
+            None => panic!("No uptime for node {} (uptimes known for {} nodes).", node, self.uptimes.len()),
+        }
+    }
+
+    pub fn num_nodes(&self) -> usize {
+        self.uptimes.len()
+    }
+
+    // Returns the exact probability that fewer than a strong quorum of the sample's seats are online.
+    // All seats of a node are online or offline together.
+    pub fn offline_probability<D: StakeDistribution>(&self, sample: &ValidatorSample<D>) -> f64 {
+        let trials: Vec<(f64, usize)> = sample.seats_by_node().into_iter()
+            .map(|(node, seats)| (self.uptime(node), seats))
+            .collect();
+        let quorum_size = sample.strong_quorum_size();
+        if quorum_size == 0 {
+            return 0.0;
+        }
+
+        poisson_binomial_cdf(&trials, quorum_size - 1)
+    }
+
+    // Returns the probability that fewer than a strong quorum of seats of a sample of the given
+    // size are online. The exact per-sample probability (see offline_probability()) is averaged over
+    // the given number of samples.
+    pub fn not_live_probability<D: StakeDistribution>(&self, distribution: &D, sample_size: usize, iterations: usize) -> f64 {
+        let total: f64 = (0..iterations)
+            .map(|_| self.offline_probability(&ValidatorSample::new(sample_size, distribution)))
+            .sum();
+
+        total / iterations as f64
+    }
+
+    // Repeatedly samples validators and their availability and returns how many times (relative to
+    // the number of iterations) fewer than a strong quorum of the sample's seats were online.
+    pub fn simulate_not_live<D: StakeDistribution>(&self, distribution: &D, sample_size: usize, iterations: usize) -> f64 {
+        let mut rng = rand::rng();
+        let mut failures = 0;
+        for _ in 0..iterations {
+            let sample = ValidatorSample::new(sample_size, distribution);
+            let online_seats: usize = sample.seats_by_node().into_iter()
+                .filter(|&(node, _)| rng.random_bool(self.uptime(node)))
+                .map(|(_, seats)| seats)
+                .sum();
+            if online_seats < sample.strong_quorum_size() {
+                failures += 1;
+            }
+        }
+
+        (failures as f64) / (iterations as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strength::stake_distribution::UniformStakeDistribution;
+
+    #[test]
+    fn offline_probability_of_single_node() {
+        // All seats of a sample from a single node are online or offline together.
+        let distribution = UniformStakeDistribution::new(1);
+        let sample = ValidatorSample::new(10, &distribution);
+        let availability = Availability::uniform(1, 0.9);
+        assert!((availability.offline_probability(&sample) - 0.1).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,44 @@
+use rand::distr::Distribution;
+use rand_distr::Binomial;
+use crate::representativeness::lifetime::lifetime_failure_probability;
+
+// An adversary that can bias the randomness used for sampling (e.g., the last revealer of a
+// randomness beacon) chooses the best out of k independently drawn samples. The adversary
+// succeeds if any of the k samples contains too many faulty validators.
+
+// Returns the probability that at least one of k independent samples fails,
+// if each fails with probability p, i.e., 1-(1-p)^k.
+// This is the same as failing during a lifetime of k committees.
+pub fn grinding_failure_probability(p: f64, k: u32) -> f64 {
+    lifetime_failure_probability(p, k as f64)
+}
+
+// Same as simulate_prob(), except that in each iteration k samples are drawn and the iteration
+// counts as a failure if at least one of them contains a fraction of at least fault_threshold
+// faulty validators.
+pub fn simulate_grinding_prob(n: u64, p: f64, fault_threshold: f64, k: usize, iterations: usize) -> f64 {
+    let mut rng = rand::rng();
+    let bin = Binomial::new(n, p).unwrap();
+    let min_faults = ((n as f64) * fault_threshold).ceil() as u64;
+
+    let mut failures = 0;
+    for _ in 0..iterations {
+        if (0..k).any(|_| bin.sample(&mut rng) >= min_faults) {
+            failures += 1;
+        }
+    }
+
+    (failures as f64) / (iterations as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grinding_probability() {
+        assert!((grinding_failure_probability(0.1, 1) - 0.1).abs() < 1e-15);
+        assert!((grinding_failure_probability(0.1, 2) - 0.19).abs() < 1e-15);
+        assert!((grinding_failure_probability(1e-20, 1000) - 1e-17).abs() < 1e-30);
+    }
+}
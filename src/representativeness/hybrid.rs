@@ -0,0 +1,119 @@
+use num_bigint::BigInt;
+use num_integer::binomial;
+use num_rational::BigRational;
+use rand::distr::Distribution;
+use rand_distr::Binomial;
+use rand_distr::num_traits::{One, Zero};
+
+// In the hybrid fault model, each seat of a validator sample is independently held by a Byzantine
+// node with probability byzantine, by a crashed (offline, but otherwise correct) node with
+// probability crashed, and by a correct node otherwise. The numbers of Byzantine and crashed
+// seats thus follow a trinomial distribution.
+
+// A condition under which a sample of size n with b Byzantine and c crashed seats violates a
+// property (e.g., safety or liveness), namely:
+// byzantine_weight * b + crash_weight * c >= (threshold_num / threshold_denom) * n
+pub struct ViolationCondition {
+    pub byzantine_weight: u32,
+    pub crash_weight: u32,
+    pub threshold_num: u32,
+    pub threshold_denom: u32,
+}
+
+impl ViolationCondition {
+    // Safety is violated if the Byzantine seats alone form a strong quorum (at least 2/3 of the seats).
+    // Crashed nodes do not endanger safety.
+    pub fn default_safety() -> Self {
+        Self { byzantine_weight: 1, crash_weight: 0, threshold_num: 2, threshold_denom: 3 }
+    }
+
+    // Liveness is violated if the correct seats that are online do not form a strong quorum,
+    // i.e., if at least 1/3 of the seats are Byzantine or crashed.
+    pub fn default_liveness() -> Self {
+        Self { byzantine_weight: 1, crash_weight: 1, threshold_num: 1, threshold_denom: 3 }
+    }
+
+    pub fn is_violated(&self, n: u32, byzantine: u32, crashed: u32) -> bool {
+        let weighted_faults = (self.byzantine_weight * byzantine + self.crash_weight * crashed) as u64;
+        weighted_faults * self.threshold_denom as u64 >= (self.threshold_num * n) as u64
+    }
+}
+
+// Returns the exact probability that a sample of size n violates the given condition, if each seat
+// is Byzantine with probability byzantine and crashed with probability crashed.
+pub fn hybrid_failure_probability(
+    n: u32,
+    byzantine: &BigRational,
+    crashed: &BigRational,
+    condition: &ViolationCondition,
+) -> BigRational {
+    let correct = BigRational::one() - byzantine - crashed;
+    let powers = |p: &BigRational| -> Vec<BigRational> {
+        (0..=n).scan(BigRational::one(), |power, _| {
+            let current = power.clone();
+            *power *= p;
+            Some(current)
+        }).collect()
+    };
+    let byzantine_powers = powers(byzantine);
+    let crashed_powers = powers(crashed);
+    let correct_powers = powers(&correct);
+
+    let mut result = BigRational::zero();
+    for b in 0..=n {
+        for c in 0..=(n - b) {
+            if !condition.is_violated(n, b, c) {
+                continue;
+            }
+            let multinomial = binomial(BigInt::from(n), BigInt::from(b)) * binomial(BigInt::from(n - b), BigInt::from(c));
+            result += BigRational::from(multinomial)
+                * &byzantine_powers[b as usize]
+                * &crashed_powers[c as usize]
+                * &correct_powers[(n - b - c) as usize];
+        }
+    }
+
+    result
+}
+
+// Repeatedly samples the numbers of Byzantine and crashed seats of a sample of size n and returns
+// how many times (relative to the number of iterations) the given condition was violated.
+pub fn simulate_hybrid_prob(n: u32, byzantine: f64, crashed: f64, condition: &ViolationCondition, iterations: usize) -> f64 {
+    let mut rng = rand::rng();
+    let byzantine_bin = Binomial::new(n as u64, byzantine).unwrap();
+    // Conditioned on a seat not being Byzantine, it is crashed with this probability.
+    let crashed_given_not_byzantine = if byzantine < 1.0 { crashed / (1.0 - byzantine) } else { 0.0 };
+
+    let mut failures = 0;
+    for _ in 0..iterations {
+        let b = byzantine_bin.sample(&mut rng);
+        let c = Binomial::new(n as u64 - b, crashed_given_not_byzantine).unwrap().sample(&mut rng);
+        if condition.is_violated(n, b as u32, c as u32) {
+            failures += 1;
+        }
+    }
+
+    (failures as f64) / (iterations as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::representativeness::failure_probability;
+    use crate::test_util::rational;
+
+    #[test]
+    fn hybrid_without_crashes_is_binomial() {
+        let probability = hybrid_failure_probability(30, &rational(1, 3), &rational(0, 1), &ViolationCondition::default_liveness());
+        assert_eq!(probability, failure_probability(30, 1, 3, 1.0/3.0));
+    }
+
+    #[test]
+    fn hybrid_crashes_count_for_liveness_only() {
+        // With 1/5 Byzantine and 1/10 crashed seats, liveness fails like with 3/10 Byzantine seats.
+        let liveness = hybrid_failure_probability(30, &rational(1, 5), &rational(1, 10), &ViolationCondition::default_liveness());
+        assert_eq!(liveness, failure_probability(30, 3, 10, 1.0/3.0));
+        let safety = hybrid_failure_probability(30, &rational(1, 5), &rational(1, 10), &ViolationCondition::default_safety());
+        assert_eq!(safety, failure_probability(30, 1, 5, 2.0/3.0));
+    }
+}
@@ -0,0 +1,157 @@
+use std::ops::AddAssign;
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use rand::distr::Distribution;
+use rand::Rng;
+use rand_distr::Binomial;
+use rand_distr::num_traits::Zero;
+use crate::representativeness::poisson_binomial_pmf;
+use crate::strength::stake_distribution::StakeDistribution;
+use crate::strength::validator_sample::ValidatorSample;
+
+// The number of faulty seats of a sample of n seats falls into exactly one of three regions:
+// - correct: fewer than n/3 faulty seats, i.e., the sample is both safe and live,
+// - not live only: at least n/3, but fewer than 2n/3 faulty seats, i.e., the sample is safe but
+//   not live,
+// - not safe: at least 2n/3 faulty seats (which also makes the sample not live).
+// The probabilities of the regions add up to 1.
+pub struct Regions<T> {
+    pub correct: T,
+    pub not_live_only: T,
+    pub not_safe: T,
+}
+
+enum Region {
+    Correct,
+    NotLiveOnly,
+    NotSafe,
+}
+
+// Returns the region of a sample with n seats, out of which the given number are faulty.
+fn region(n: usize, faulty: usize) -> Region {
+    if faulty >= (2 * n).div_ceil(3) {
+        Region::NotSafe
+    } else if faulty >= n.div_ceil(3) {
+        Region::NotLiveOnly
+    } else {
+        Region::Correct
+    }
+}
+
+impl<T: Zero + AddAssign> Regions<T> {
+    fn zero() -> Self {
+        Regions { correct: T::zero(), not_live_only: T::zero(), not_safe: T::zero() }
+    }
+
+    // Adds the given amount (e.g., a probability mass or a count) to the given region.
+    fn add(&mut self, region: Region, amount: T) {
+        match region {
+            Region::Correct => self.correct += amount,
+            Region::NotLiveOnly => self.not_live_only += amount,
+            Region::NotSafe => self.not_safe += amount,
+        }
+    }
+}
+
+// Returns the probabilities of the regions, given the probability mass function of the number of
+// faulty seats out of n (pmf[k] being the probability of exactly k faulty seats).
+pub fn regions_from_pmf<T: Clone + Zero + AddAssign>(n: usize, pmf: &[T]) -> Regions<T> {
+    let mut regions = Regions::zero();
+    for (k, mass) in pmf.iter().enumerate() {
+        regions.add(region(n, k), mass.clone());
+    }
+
+    regions
+}
+
+// Returns the exact probabilities of the regions for a sample of size n, if each seat is faulty
+// independently with probability f = f_num / f_denom.
+pub fn joint_probabilities(n: usize, f_num: u32, f_denom: u32) -> Regions<BigRational> {
+    let f = BigRational::new(BigInt::from(f_num), BigInt::from(f_denom));
+    regions_from_pmf(n, &poisson_binomial_pmf(&vec![(f, 1); n]))
+}
+
+// Same as joint_probabilities(), but for samples from a stake distribution, where each node is
+// faulty independently with probability p (and all seats of a faulty node are faulty). The exact
+// probabilities of each random sample are averaged over the given number of samples, so the result
+// is an estimate.
+pub fn expected_node_joint_probabilities<D: StakeDistribution>(distribution: &D, n: usize, p: f64, iterations: usize) -> Regions<f64> {
+    let mut total = Regions::zero();
+    for _ in 0..iterations {
+        let sample = ValidatorSample::new(n, distribution);
+        let trials: Vec<(f64, usize)> = sample.seats_by_node().into_iter().map(|(_, seats)| (p, seats)).collect();
+        let regions = regions_from_pmf(n, &poisson_binomial_pmf(&trials));
+        total.correct += regions.correct;
+        total.not_live_only += regions.not_live_only;
+        total.not_safe += regions.not_safe;
+    }
+
+    relative(total, iterations)
+}
+
+fn relative(counts: Regions<f64>, iterations: usize) -> Regions<f64> {
+    Regions {
+        correct: counts.correct / iterations as f64,
+        not_live_only: counts.not_live_only / iterations as f64,
+        not_safe: counts.not_safe / iterations as f64,
+    }
+}
+
+// Simulates joint_probabilities(): repeatedly samples the number of faulty seats and returns how
+// many times (relative to the number of iterations) it fell into each region.
+pub fn simulate_joint(n: usize, p: f64, iterations: usize) -> Regions<f64> {
+    let mut rng = rand::rng();
+    let bin = Binomial::new(n as u64, p).unwrap();
+    let mut counts = Regions::zero();
+    for _ in 0..iterations {
+        counts.add(region(n, bin.sample(&mut rng) as usize), 1.0);
+    }
+
+    relative(counts, iterations)
+}
+
+// Simulates expected_node_joint_probabilities().
+pub fn simulate_node_joint<D: StakeDistribution>(distribution: &D, n: usize, p: f64, iterations: usize) -> Regions<f64> {
+    let mut rng = rand::rng();
+    let mut counts = Regions::zero();
+    for _ in 0..iterations {
+        let sample = ValidatorSample::new(n, distribution);
+        let faulty: usize = sample.seats_by_node().into_iter()
+            .filter(|_| rng.random_bool(p))
+            .map(|(_, seats)| seats)
+            .sum();
+        counts.add(region(n, faulty), 1.0);
+    }
+
+    relative(counts, iterations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_distr::num_traits::One;
+    use crate::representativeness::failure_probability;
+    use crate::strength::stake_distribution::UniformStakeDistribution;
+
+    #[test]
+    fn regions_match_failure_probabilities() {
+        for n in [90, 111] {
+            let regions = joint_probabilities(n, 1, 3);
+            assert_eq!(&regions.correct + &regions.not_live_only + &regions.not_safe, BigRational::one());
+            assert_eq!(regions.not_safe, failure_probability(n as u32, 1, 3, 2.0/3.0));
+            assert_eq!(&regions.not_live_only + &regions.not_safe, failure_probability(n as u32, 1, 3, 1.0/3.0));
+        }
+    }
+
+    #[test]
+    fn node_regions() {
+        // All seats of a sample from a single node are faulty together.
+        let regions = expected_node_joint_probabilities(&UniformStakeDistribution::new(1), 9, 0.25, 10);
+        assert!((regions.correct - 0.75).abs() < 1e-12);
+        assert_eq!(regions.not_live_only, 0.0);
+        assert!((regions.not_safe - 0.25).abs() < 1e-12);
+
+        let regions = expected_node_joint_probabilities(&UniformStakeDistribution::new(3), 9, 0.25, 10);
+        assert!((regions.correct + regions.not_live_only + regions.not_safe - 1.0).abs() < 1e-12);
+    }
+}
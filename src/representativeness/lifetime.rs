@@ -0,0 +1,72 @@
+use crate::representativeness::min_sample_size;
+
+// The lifetime of a system during which validator samples (committees) are regularly replaced.
+pub struct Lifetime {
+    pub years: f64,
+    pub committees_per_day: f64,
+}
+
+impl Lifetime {
+    // Returns the number of committees selected during the lifetime.
+    pub fn num_committees(&self) -> f64 {
+        self.years * 365.25 * self.committees_per_day
+    }
+}
+
+// Returns the probability that at least one of num_committees independently selected committees
+// fails, if each fails with probability p, i.e., 1-(1-p)^num_committees.
+// Computed as -expm1(k * ln_1p(-p)) to stay precise for the tiny values of p we are interested in.
+pub fn lifetime_failure_probability(p: f64, num_committees: f64) -> f64 {
+    -(num_committees * (-p).ln_1p()).exp_m1()
+}
+
+// Same as lifetime_failure_probability(), but using the union bound num_committees * p.
+pub fn lifetime_failure_union_bound(p: f64, num_committees: f64) -> f64 {
+    (num_committees * p).min(1.0)
+}
+
+// Inverse of lifetime_failure_probability(). Returns the highest per-committee failure probability
+// such that the probability of any of num_committees committees failing is at most budget.
+pub fn per_committee_target(budget: f64, num_committees: f64) -> f64 {
+    -((-budget).ln_1p() / num_committees).exp_m1()
+}
+
+// Inverse of lifetime_failure_union_bound().
+pub fn per_committee_target_union_bound(budget: f64, num_committees: f64) -> f64 {
+    budget / num_committees
+}
+
+// Returns the minimal sample size for which the probability of any committee failing during the
+// lifetime is lower than budget. The other parameters are passed directly to min_sample_size().
+pub fn min_lifetime_sample_size(
+    lifetime: &Lifetime,
+    budget: f64,
+    f_num: u32,
+    f_denom: u32,
+    fault_threshold: f64,
+    max_sample_size: u32,
+) -> Option<u32> {
+    let max_prob = per_committee_target(budget, lifetime.num_committees());
+    min_sample_size(f_num, f_denom, fault_threshold, max_prob, max_sample_size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx_equal_rel(a: f64, b: f64) -> bool {
+        ((a - b) / b).abs() < 1e-9
+    }
+
+    #[test]
+    fn lifetime_derivation() {
+        let lifetime = Lifetime { years: 10.0, committees_per_day: 1440.0 };
+        assert_eq!(lifetime.num_committees(), 5_259_600.0);
+
+        let p = per_committee_target(1e-6, lifetime.num_committees());
+        assert!(approx_equal_rel(lifetime_failure_probability(p, lifetime.num_committees()), 1e-6));
+        assert!((p - 1.9e-13).abs() < 0.01e-13);
+        assert!(p >= per_committee_target_union_bound(1e-6, lifetime.num_committees()));
+        assert!(lifetime_failure_union_bound(p, lifetime.num_committees()) >= 1e-6);
+    }
+}
@@ -0,0 +1,858 @@
+pub mod multi_epoch;
+pub mod lifetime;
+pub mod sybil;
+pub mod hybrid;
+pub mod availability;
+pub mod grinding;
+pub mod sortition;
+pub mod weighted_voting;
+pub mod top_k;
+pub mod quorum_optimizer;
+pub mod joint;
+
+use rand_distr::Binomial;
+use num_rational::BigRational;
+use num_integer::binomial;
+use num_bigint::BigInt;
+use rand::distr::Distribution;
+use rand_distr::num_traits::{FromPrimitive, One, ToPrimitive, Zero};
+use rand::Rng;
+use crate::strength::entity_grouping::EntityGrouping;
+use crate::strength::stake_distribution::{CustomStakeDistribution, StakeDistribution};
+use crate::strength::validator_sample::{Selection, ValidatorSample, VoteWeight};
+use crate::strength::churn::*;
+use multi_epoch::MultiEpochSimulation;
+use lifetime::*;
+use sybil::*;
+use hybrid::*;
+use availability::Availability;
+use grinding::*;
+use sortition::*;
+use weighted_voting::simulate_vote_weight_prob;
+use top_k::*;
+use quorum_optimizer::pareto_quorums;
+use joint::*;
+use crate::strength::capping::CappedStakeDistribution;
+use crate::table::Table;
+use std::fs;
+use std::path::Path;
+use std::ops::{Mul, Sub};
+
+// Repeatedly samples a binomially distributed random variable and returns
+// how many times (relative to the total number of samples) the value reached a given
+// fault_threshold parameter. The random variable represents the number of faulty validators
+// in a validator sample and the fault threshold expresses the fraction of faulty validators
+// at which the sample fails, counted the same way as in failure_probability().
+pub fn simulate_prob(n: u64, p: f64, fault_threshold: f64, iterations: usize) -> f64 {
+    let mut rng = rand::rng();
+    let bin = Binomial::new(n, p).unwrap();
+    let min_faults = ((n as f64) * fault_threshold).ceil() as u64;
+
+    let mut failures = 0;
+    for _ in 0..iterations {
+        let k: u64 = bin.sample(&mut rng);
+        if k >= min_faults {
+            failures += 1;
+       }
+    }
+
+    (failures as f64) / (iterations as f64)
+}
+
+// Repeatedly samples validators from a stake distribution and returns how many times (relative to
+// the total number of samples) the fraction of seats held by faulty validators reached
+// fault_threshold. Each entity of the grouping is faulty independently with probability p and all
+// nodes of a faulty entity are faulty. Unlike in simulate_prob, where each seat is faulty
+// independently, failures are correlated among the seats of the same node or entity.
+pub fn simulate_entity_prob<D: StakeDistribution>(
+    distribution: &D,
+    grouping: &EntityGrouping,
+    n: usize,
+    p: f64,
+    fault_threshold: f64,
+    iterations: usize,
+) -> f64 {
+    let mut rng = rand::rng();
+    let min_faults = ((n as f64) * fault_threshold).ceil() as usize;
+
+    let mut failures = 0;
+    for _ in 0..iterations {
+        let sample = ValidatorSample::new(n, distribution);
+        let mut faulty = vec![None; grouping.num_entities()];
+        let faults = sample.nodes().iter()
+            .filter(|&&node| *faulty[grouping.entity_of(node)].get_or_insert_with(|| rng.random_bool(p)))
+            .count();
+        if faults >= min_faults {
+            failures += 1;
+        }
+    }
+
+    (failures as f64) / (iterations as f64)
+}
+
+// Probability mass function of the binomial distribution Bin(n, p),
+// evaluated at k. Returns the probability that,
+// out of n Bernoulli trials wit success probability p,
+// there will be exactly k successes.
+fn bin_probability_mass(n: u32, p: &BigRational, k: u32) -> BigRational {
+    if k > n {
+        return BigRational::from_i32(0).unwrap();
+    }
+
+    let bin_coeff = binomial(BigInt::from(n), BigInt::from(k));
+
+    BigRational::from(bin_coeff) * p.pow(k as i32) * (BigRational::one() - p).pow((n-k) as i32)
+}
+
+// Binomial cumulative distribution function. Returns the probability that,
+// out of n Bernoulli trials with success probability p,
+// there will be at most k successes.
+fn bin_probability_cdf(n: u32, p: &BigRational, k: u32) -> BigRational {
+    let mut result = BigRational::zero();
+
+
+    for i in 0..=k {
+        result += bin_probability_mass(n, p, i);
+    }
+
+    result
+}
+
+// Probability mass function of the (weighted) Poisson binomial distribution, i.e., of the number
+// of successes out of independent Bernoulli trials with (possibly different) success probabilities.
+// Each trial is given as a pair of its success probability and its weight, where a successful trial
+// counts as weight successes (e.g., a node holding multiple seats of a sample, all of which are
+// faulty or correct together). With all weights being 1, this is the classic Poisson binomial
+// distribution. Returns a vector whose k-th element is the probability of exactly k successes.
+// Computed by dynamic programming over the trials, in O(number of trials * total weight)
+// operations. Works with both exact (BigRational) and floating point (f64) arithmetic.
+pub fn poisson_binomial_pmf<T>(trials: &[(T, usize)]) -> Vec<T>
+where
+    T: Clone + Zero + One + Sub<Output = T>,
+    for<'a> &'a T: Mul<&'a T, Output = T>,
+{
+    // pmf[k] is the probability of exactly k successes among the trials processed so far.
+    let mut pmf = vec![T::one()];
+    for (p, weight) in trials {
+        let q = T::one() - p.clone();
+        let mut next = vec![T::zero(); pmf.len() + weight];
+        for (k, mass) in pmf.iter().enumerate() {
+            next[k] = next[k].clone() + mass * &q;
+            next[k + weight] = next[k + weight].clone() + mass * p;
+        }
+        pmf = next;
+    }
+
+    pmf
+}
+
+// Poisson binomial cumulative distribution function. Returns the probability that,
+// out of independent (weighted) Bernoulli trials as in poisson_binomial_pmf(),
+// there will be at most k successes.
+pub fn poisson_binomial_cdf<T>(trials: &[(T, usize)], k: usize) -> T
+where
+    T: Clone + Zero + One + Sub<Output = T>,
+    for<'a> &'a T: Mul<&'a T, Output = T>,
+{
+    poisson_binomial_pmf(trials).into_iter().take(k + 1).fold(T::zero(), |sum, mass| sum + mass)
+}
+
+// Poisson binomial tail probabilities. Returns a vector whose k-th element is the probability that,
+// out of independent (weighted) Bernoulli trials as in poisson_binomial_pmf(), there will be at
+// least k successes (for k up to the total weight plus one, where it is zero).
+// The tail is summed directly from the probability mass function. In floating point arithmetic,
+// this keeps small probabilities precise, while 1 - cdf would cancel to 0 (or noise) below
+// about 1e-16.
+pub fn poisson_binomial_tail<T>(trials: &[(T, usize)]) -> Vec<T>
+where
+    T: Clone + Zero + One + Sub<Output = T>,
+    for<'a> &'a T: Mul<&'a T, Output = T>,
+{
+    let pmf = poisson_binomial_pmf(trials);
+    let mut tail = vec![T::zero(); pmf.len() + 1];
+    for k in (0..pmf.len()).rev() {
+        tail[k] = tail[k + 1].clone() + pmf[k].clone();
+    }
+
+    tail
+}
+
+// Returns the exact probability that a given validator sample contains a fraction of at least
+// fault_threshold faulty seats, if each entity of the grouping is faulty independently with
+// probability p and all nodes of a faulty entity are faulty.
+pub fn entity_failure_probability<D: StakeDistribution>(
+    sample: &ValidatorSample<D>,
+    grouping: &EntityGrouping,
+    p: f64,
+    fault_threshold: f64,
+) -> f64 {
+    let min_faults = ((sample.nodes().len() as f64) * fault_threshold).ceil() as usize;
+    if min_faults == 0 {
+        return 1.0;
+    }
+    let mut entity_seats = vec![0; grouping.num_entities()];
+    for (node, seats) in sample.seats_by_node() {
+        entity_seats[grouping.entity_of(node)] += seats;
+    }
+    let trials: Vec<(f64, usize)> = entity_seats.into_iter().filter(|&s| s > 0).map(|s| (p, s)).collect();
+
+    poisson_binomial_tail(&trials)[min_faults]
+}
+
+// Same as simulate_entity_prob(), but instead of sampling which entities are faulty, computes
+// the exact failure probability of each sample (see entity_failure_probability()) and returns
+// its average over the given number of samples.
+pub fn expected_entity_failure_probability<D: StakeDistribution>(
+    distribution: &D,
+    grouping: &EntityGrouping,
+    n: usize,
+    p: f64,
+    fault_threshold: f64,
+    iterations: usize,
+) -> f64 {
+    let total: f64 = (0..iterations)
+        .map(|_| entity_failure_probability(&ValidatorSample::new(n, distribution), grouping, p, fault_threshold))
+        .sum();
+
+    total / iterations as f64
+}
+
+// Returns the probability that a validator sample of a given size, taken from a set of nodes with
+// a fraction f (expressed a rational number f_num/f_denom) contains a fraction of at least
+// fault_threshold faulty validators.
+pub fn failure_probability(sample_size: u32, f_num: u32, f_denom: u32, fault_threshold: f64) -> BigRational {
+    let f = BigRational::new(BigInt::from(f_num), BigInt::from(f_denom));
+    failure_probability_for_fraction(sample_size, &f, fault_threshold)
+}
+
+// Same as failure_probability(), but with f given as an arbitrary rational number.
+pub fn failure_probability_for_fraction(sample_size: u32, f: &BigRational, fault_threshold: f64) -> BigRational {
+    let max_faults = ((sample_size as f64) * fault_threshold).ceil() as u32 - 1;
+
+    BigRational::one() - &bin_probability_cdf(sample_size, f, max_faults)
+}
+
+// Returns the minimal sample size smaller than max_sample_size for which the failure probability
+// (as computed by the failure_probability function) is lower than max_prob. f_num, f_denom, and
+// fault_threshold are passed directly to failure_probability. If no sample size up to
+// max_sample_size satisfies this condition, returns None.
+pub fn min_sample_size(f_num: u32, f_denom: u32, fault_threshold: f64, max_prob: f64, max_sample_size: u32) -> Option<u32> {
+    for n in 1..=max_sample_size {
+        let prob = failure_probability(n, f_num, f_denom, fault_threshold).to_f64().unwrap();
+        if prob < max_prob {
+            return Some(n);
+        }
+    }
+
+    None
+}
+
+// The inverse of min_sample_size(): returns the largest fraction f of faulty stake for which the
+// failure probability of a sample of the given size (as computed by failure_probability_for_fraction)
+// is lower than max_prob. Since the failure probability grows with f, f is found by bisection on
+// [0, 1] and the result is exact up to precision_bits binary digits, i.e., it is a multiple of
+// 2^-precision_bits and adding 2^-precision_bits to it would reach max_prob.
+// Returns None if even f = 0 does not satisfy the condition.
+pub fn max_faulty_fraction(sample_size: u32, fault_threshold: f64, max_prob: f64, precision_bits: u32) -> Option<BigRational> {
+    let max_prob = BigRational::from_float(max_prob).unwrap();
+    let is_safe = |f: &BigRational| failure_probability_for_fraction(sample_size, f, fault_threshold) < max_prob;
+    let mut low = BigRational::zero();
+    let mut high = BigRational::one();
+    if !is_safe(&low) {
+        return None;
+    }
+    if is_safe(&high) {
+        return Some(high);
+    }
+
+    let two = BigRational::from_integer(BigInt::from(2));
+    for _ in 0..precision_bits {
+        let middle = (&low + &high) / &two;
+        if is_safe(&middle) {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+
+    Some(low)
+}
+
+pub fn compute() {
+    let sim_iterations = 100000;
+    let output_directory = "output";
+    let max_fraction_precision_bits = 32;
+    let quorum_safety_target = 1.9e-16;
+    let quorum_liveness_target = 1e-3;
+    let quorum_max_sample_size = 300;
+    let quorum_max_printed_choices = 10;
+    let joint_node_iterations = 10000;
+    let correlated_sim_iterations = 10000;
+    let actual_distribution_file = "node-stakes-september-2025";
+    let entity_file = "node-entities-september-2025";
+    let multi_epoch_iterations = 200;
+    let split_sim_iterations = 10000;
+    let uptime_file = "node-uptime-september-2025";
+    let availability_iterations = 10000;
+    let max_grinding_sim_draws = 16;
+    let sortition_stake_units = 1_000_000_000;
+    let sortition_sim_iterations = 10000;
+    let vote_weight_sim_iterations = 10000;
+    let top_k_sim_iterations = 10000;
+
+    println!();
+    println!("================================================================================");
+    println!("SAMPLE REPRESENTATIVENESS");
+    println!("I.e.: What is the probability of a sample containing too many faulty validators?");
+    println!("================================================================================");
+    println!();
+
+    println!("Finding smallest sample size that is safe for f = 1/3 with failure probability 1.9e-13.");
+    if let Some(sample_size) = min_sample_size(1, 3, 2.0/3.0, 1.9e-13, 200) {
+        println!("Minimum sample size: {}", sample_size);
+    } else {
+        println!("No sample size is safe under given parameters.");
+    }
+    println!("Finding smallest sample size that is safe for f = 1/3 with failure probability 1.9e-16.");
+    if let Some(sample_size) = min_sample_size(1, 3, 2.0/3.0, 1.9e-16, 200) {
+        println!("Minimum sample size: {}", sample_size);
+    } else {
+        println!("No sample size is safe under given parameters.");
+    }
+    println!("Finding smallest sample size that is live for f = 1/5 with failure probability 1e-3.");
+    if let Some(sample_size) = min_sample_size(1, 5, 1.0/3.0, 1e-3, 200) {
+        println!("Minimum sample size: {}", sample_size);
+    } else {
+        println!("No sample size is safe under given parameters.");
+    }
+    println!();
+    for (sample_size, fault_threshold, threshold_name, max_prob) in [
+        (111, 2.0/3.0, "safe", 1.9e-13),
+        (141, 2.0/3.0, "safe", 1.9e-16),
+        (111, 1.0/3.0, "live", 1e-3),
+    ] {
+        println!("Finding largest f for which a sample of size {} is {} with failure probability {:.1e}.", sample_size, threshold_name, max_prob);
+        match max_faulty_fraction(sample_size, fault_threshold, max_prob, max_fraction_precision_bits) {
+            Some(f) => println!("Maximum f: {:.6} (exactly {})", f.to_f64().unwrap(), f),
+            None => println!("No fraction of faulty stake is tolerable under given parameters."),
+        }
+    }
+    println!();
+    let lifetime = Lifetime { years: 10.0, committees_per_day: 1440.0 };
+    println!("Failure probabilities over the lifetime of the system.");
+    println!("Lifetime: {} years with {} committees per day, i.e., {} committees.",
+             lifetime.years, lifetime.committees_per_day, lifetime.num_committees());
+    for budget in [1e-6, 1e-9] {
+        println!("Lifetime failure budget {:.0e}: per-committee target {:.3e} (union bound {:.3e}).",
+                 budget,
+                 per_committee_target(budget, lifetime.num_committees()),
+                 per_committee_target_union_bound(budget, lifetime.num_committees()),
+        );
+        match min_lifetime_sample_size(&lifetime, budget, 1, 3, 2.0/3.0, 200) {
+            Some(sample_size) => println!("Minimum sample size safe for f = 1/3 over the lifetime: {}", sample_size),
+            None => println!("No sample size is safe over the lifetime under given parameters."),
+        }
+    }
+    for sample_size in [141, 111] {
+        let p = failure_probability(sample_size, 1, 3, 2.0/3.0).to_f64().unwrap();
+        println!("Sample size {} with f = 1/3: not safe {:.2e} per committee, {:.3e} over the lifetime (union bound {:.3e}).",
+                 sample_size,
+                 p,
+                 lifetime_failure_probability(p, lifetime.num_committees()),
+                 lifetime_failure_union_bound(p, lifetime.num_committees()),
+        );
+    }
+    println!();
+    println!("Probabilities of samples being safe and live, for various parameters.");
+    println!("n: sample size");
+    println!("f: assumed system-wide fraction of stake controlled by malicious nodes");
+    println!("A sample not safe if at least 2/3 of its nodes are faulty.");
+    println!("A sample not live if at least 1/3 of its nodes are faulty.");
+    println!();
+    let mut table = Table::new(&["n", "f", "not safe", "not live", "type"]);
+    for (n, f_num, f_denom) in [(141, 1, 3), (111, 1, 3), (111, 1, 5), (90, 1, 5)] {
+        let f = f_num as f64 / f_denom as f64;
+        table.add_row(vec![
+            n.to_string(),
+            format!("{}/{}", f_num, f_denom),
+            format!("{:.2e}", failure_probability(n, f_num, f_denom, 2.0/3.0).to_f64().unwrap()),
+            format!("{:.2e}", failure_probability(n, f_num, f_denom, 1.0/3.0).to_f64().unwrap()),
+            "Calc".to_owned(),
+        ]);
+        table.add_row(vec![
+            n.to_string(),
+            format!("{}/{}", f_num, f_denom),
+            format!("{:.2e}", simulate_prob(n as u64, f, 2.0/3.0, sim_iterations)),
+            format!("{:.2e}", simulate_prob(n as u64, f, 1.0/3.0, sim_iterations)),
+            "Sim".to_owned(),
+        ]);
+    }
+    print!("{}", table.to_terminal());
+    fs::create_dir_all(output_directory).unwrap();
+    let markdown_file = format!("{}/representativeness.md", output_directory);
+    let latex_file = format!("{}/representativeness.tex", output_directory);
+    fs::write(&markdown_file, table.to_markdown()).unwrap();
+    fs::write(&latex_file, table.to_latex()).unwrap();
+    println!("Also written as Markdown to '{}' and as LaTeX (booktabs) to '{}'.", markdown_file, latex_file);
+    println!();
+    println!("Pareto-optimal sample sizes n and quorum sizes q > n/2 for which the sample is not safe (at");
+    println!("least 2q - n faulty seats, i.e., the faulty seats can cover the intersection of two quorums)");
+    println!("with probability below {:.1e} and not live (the correct seats do not form a quorum) with", quorum_safety_target);
+    println!("probability below {:.1e}, where each seat is faulty independently with probability f. Each n is", quorum_liveness_target);
+    println!("listed with its smallest valid q, and only if no smaller sample allows a quorum of at most the");
+    println!("same fraction q/n of the seats.");
+    for (f_num, f_denom) in [(1, 5), (1, 4), (1, 3)] {
+        println!();
+        println!("f = {}/{}:", f_num, f_denom);
+        let choices = pareto_quorums(f_num as f64 / f_denom as f64, quorum_safety_target, quorum_liveness_target, quorum_max_sample_size);
+        if choices.is_empty() {
+            println!("A sample size greater than {} would be needed.", quorum_max_sample_size);
+            continue;
+        }
+        let mut table = Table::new(&["n", "q", "q/n", "not safe", "not live"]);
+        let mut full_table = Table::new(&["n", "q", "q/n", "not safe", "not live"]);
+        for (i, choice) in choices.iter().enumerate() {
+            let row = vec![
+                choice.sample_size.to_string(),
+                choice.quorum_size.to_string(),
+                format!("{:.4}", choice.quorum_size as f64 / choice.sample_size as f64),
+                format!("{:.2e}", choice.not_safe),
+                format!("{:.2e}", choice.not_live),
+            ];
+            if i < quorum_max_printed_choices {
+                table.add_row(row.clone());
+            }
+            full_table.add_row(row);
+        }
+        print!("{}", table.to_terminal());
+        let markdown_file = format!("{}/quorums-f-{}-{}.md", output_directory, f_num, f_denom);
+        fs::write(&markdown_file, full_table.to_markdown()).unwrap();
+        println!("Smallest {} of {} Pareto-optimal choices shown, all written to '{}'.",
+                 choices.len().min(quorum_max_printed_choices), choices.len(), markdown_file);
+    }
+
+    println!();
+    println!("Probabilities of samples from the actual distribution (file '{}') not being live,", actual_distribution_file);
+    println!("if failures are correlated. Each seat, node, or entity is faulty independently with");
+    println!("probability f. All seats of a faulty node and all nodes of a faulty entity are faulty.");
+    println!("For nodes and entities, Calc is the exact probability for each sample, averaged over the samples.");
+    println!();
+    let actual_distribution = CustomStakeDistribution::from_file(actual_distribution_file);
+    let per_node = EntityGrouping::per_node(&actual_distribution);
+    let grouping = EntityGrouping::load(entity_file, &actual_distribution);
+    if grouping.is_none() {
+        println!("Skipping the entity rows: no file '{}' and no operators in '{}'.", entity_file, actual_distribution_file);
+        println!();
+    }
+    let mut table = Table::new(&["n", "f", "not live", "faulty unit"]).with_left_aligned(3);
+    for (n, f_num, f_denom) in [(111, 1, 5), (90, 1, 5)] {
+        let f = f_num as f64 / f_denom as f64;
+        let mut rows = vec![
+            (failure_probability(n, f_num, f_denom, 1.0/3.0).to_f64().unwrap(), "seat (Calc)"),
+            (expected_entity_failure_probability(&actual_distribution, &per_node, n as usize, f, 1.0/3.0, correlated_sim_iterations), "node (Calc)"),
+            (simulate_entity_prob(&actual_distribution, &per_node, n as usize, f, 1.0/3.0, correlated_sim_iterations), "node (Sim)"),
+        ];
+        if let Some(grouping) = &grouping {
+            rows.push((expected_entity_failure_probability(&actual_distribution, grouping, n as usize, f, 1.0/3.0, correlated_sim_iterations), "entity (Calc)"));
+            rows.push((simulate_entity_prob(&actual_distribution, grouping, n as usize, f, 1.0/3.0, correlated_sim_iterations), "entity (Sim)"));
+        }
+        for (not_live, unit) in rows {
+            table.add_row(vec![
+                n.to_string(),
+                format!("{}/{}", f_num, f_denom),
+                format!("{:.2e}", not_live),
+                unit.to_owned(),
+            ]);
+        }
+    }
+    print!("{}", table.to_terminal());
+
+    println!();
+    println!("Probabilities of at least one sample not being safe / live over a horizon of several");
+    println!("epochs, starting from the actual distribution, with a new sample in each epoch.");
+    println!("Each node is faulty independently with probability f.");
+    println!("static: the stake does not change between epochs.");
+    println!("churn: in each epoch, on average 1 node joins, each node leaves with probability 0.005,");
+    println!("       each node loses 10% of its stake to another node with probability 0.05, and");
+    println!("       all stake grows by 0.1% of rewards.");
+    println!("Simulation iterations for each row: {}", multi_epoch_iterations);
+    println!();
+    let static_model: Vec<Box<dyn ChurnModel>> = vec![];
+    let churn_model: Vec<Box<dyn ChurnModel>> = vec![
+        Box::new(NodeJoins { joins_per_epoch: 1.0 }),
+        Box::new(NodeExits { exit_probability: 0.005 }),
+        Box::new(DelegationMoves { move_probability: 0.05, moved_fraction: 0.1 }),
+        Box::new(RewardCompounding { reward_rate: 0.001 }),
+    ];
+    let mut table = Table::new(&["n", "f", "epochs", "stake", "not safe", "not live"]);
+    for (n, f_num, f_denom) in [(111, 1, 5), (111, 1, 3)] {
+        for epochs in [1, 10, 50] {
+            for (name, model) in [("static", &static_model), ("churn", &churn_model)] {
+                let mut simulation = MultiEpochSimulation::new(&actual_distribution, model);
+                simulation.run(n, epochs, f_num as f64 / f_denom as f64, multi_epoch_iterations);
+                table.add_row(vec![
+                    n.to_string(),
+                    format!("{}/{}", f_num, f_denom),
+                    epochs.to_string(),
+                    name.to_owned(),
+                    format!("{:.2e}", simulation.not_safe_probability()),
+                    format!("{:.2e}", simulation.not_live_probability()),
+                ]);
+            }
+        }
+    }
+    print!("{}", table.to_terminal());
+
+    println!();
+    println!("Stake splitting: an adversary holding a fraction f of the stake, split across several identities,");
+    println!("joins the actual distribution. Since seats are sampled with replacement, the adversarial seats");
+    println!("follow Bin(n, f) regardless of the split, i.e., splitting neither gains nor loses seats.");
+    println!("seats: expected number of adversarial seats (Calc) / average number of adversarial seats (Sim)");
+    println!("ids: average number of distinct adversarial identities in the sample (Sim)");
+    println!("Simulation iterations for each row: {}", split_sim_iterations);
+    println!();
+    let strategies = [
+        SplittingStrategy::Single,
+        SplittingStrategy::Equal(10),
+        SplittingStrategy::Equal(100),
+        SplittingStrategy::Geometric { identities: 10, ratio: 0.5 },
+    ];
+    let mut table = Table::new(&["n", "f", "strategy", "seats", "ids", "not safe", "not live", "type"]);
+    for (n, f_num, f_denom) in [(111, 1, 5), (111, 1, 3)] {
+        let f = format!("{}/{}", f_num, f_denom);
+        table.add_row(vec![
+            n.to_string(),
+            f.clone(),
+            "any".to_owned(),
+            format!("{:.2}", n as f64 * f_num as f64 / f_denom as f64),
+            "-".to_owned(),
+            format!("{:.2e}", failure_probability(n, f_num, f_denom, 2.0/3.0).to_f64().unwrap()),
+            format!("{:.2e}", failure_probability(n, f_num, f_denom, 1.0/3.0).to_f64().unwrap()),
+            "Calc".to_owned(),
+        ]);
+        for strategy in strategies {
+            let distribution = SplitStakeDistribution::new(&actual_distribution, f_num as f64 / f_denom as f64, strategy);
+            let result = simulate_split(&distribution, n as usize, split_sim_iterations);
+            table.add_row(vec![
+                n.to_string(),
+                f.clone(),
+                strategy.name(),
+                format!("{:.2}", result.avg_adversarial_seats),
+                format!("{:.1}", result.avg_adversarial_identities),
+                format!("{:.2e}", result.not_safe),
+                format!("{:.2e}", result.not_live),
+                "Sim".to_owned(),
+            ]);
+        }
+    }
+    print!("{}", table.to_terminal());
+
+    println!();
+    println!("Probabilities of samples being safe and live with both Byzantine and crashed nodes.");
+    println!("b: assumed system-wide fraction of stake controlled by Byzantine nodes");
+    println!("c: assumed system-wide fraction of stake controlled by crashed nodes");
+    println!("A sample not safe if at least 2/3 of its nodes are Byzantine.");
+    println!("A sample not live if at least 1/3 of its nodes are Byzantine or crashed.");
+    println!();
+    let safety = ViolationCondition::default_safety();
+    let liveness = ViolationCondition::default_liveness();
+    let fraction = |num: i32, denom: i32| if num == 0 { "0".to_owned() } else { format!("{}/{}", num, denom) };
+    let mut table = Table::new(&["n", "b", "c", "not safe", "not live", "type"]);
+    for (n, (b_num, b_denom), (c_num, c_denom)) in [(111, (1, 5), (0, 1)), (111, (1, 5), (1, 10)), (111, (1, 10), (1, 5)), (141, (1, 3), (1, 10))] {
+        let b = BigRational::new(BigInt::from(b_num), BigInt::from(b_denom));
+        let c = BigRational::new(BigInt::from(c_num), BigInt::from(c_denom));
+        table.add_row(vec![
+            n.to_string(),
+            fraction(b_num, b_denom),
+            fraction(c_num, c_denom),
+            format!("{:.2e}", hybrid_failure_probability(n, &b, &c, &safety).to_f64().unwrap()),
+            format!("{:.2e}", hybrid_failure_probability(n, &b, &c, &liveness).to_f64().unwrap()),
+            "Calc".to_owned(),
+        ]);
+        table.add_row(vec![
+            n.to_string(),
+            fraction(b_num, b_denom),
+            fraction(c_num, c_denom),
+            format!("{:.2e}", simulate_hybrid_prob(n, b.to_f64().unwrap(), c.to_f64().unwrap(), &safety, sim_iterations)),
+            format!("{:.2e}", simulate_hybrid_prob(n, b.to_f64().unwrap(), c.to_f64().unwrap(), &liveness, sim_iterations)),
+            "Sim".to_owned(),
+        ]);
+    }
+    print!("{}", table.to_terminal());
+
+    println!();
+    println!("Probabilities of samples from the actual distribution not being live due to offline nodes.");
+    println!("A sample is not live if fewer than a strong quorum (n - floor(n/3)) of its seats are online.");
+    println!("Calc: exact probability for each sample, averaged over {} samples.", availability_iterations);
+    println!("Sim: fraction of {} samples with random node availability that were not live.", availability_iterations);
+    println!();
+    let mut availabilities = vec![
+        ("all 99%".to_owned(), Availability::uniform(actual_distribution.num_nodes(), 0.99)),
+        ("all 90%".to_owned(), Availability::uniform(actual_distribution.num_nodes(), 0.9)),
+        ("all 75%".to_owned(), Availability::uniform(actual_distribution.num_nodes(), 0.75)),
+    ];
+    if Path::new(uptime_file).exists() {
+        let availability = Availability::from_file(uptime_file);
+        if availability.num_nodes() != actual_distribution.num_nodes() {
+            panic!("{} uptimes in file '{}' for {} nodes.", availability.num_nodes(), uptime_file, actual_distribution.num_nodes());
+        }
+        availabilities.push((format!("file '{}'", uptime_file), availability));
+    } else {
+        println!("(No per-node uptimes: file '{}' does not exist.)", uptime_file);
+    }
+    let mut table = Table::new(&["n", "uptime", "not live (Calc)", "not live (Sim)"]);
+    for n in [111, 90] {
+        for (name, availability) in &availabilities {
+            table.add_row(vec![
+                n.to_string(),
+                name.clone(),
+                format!("{:.2e}", availability.not_live_probability(&actual_distribution, n, availability_iterations)),
+                format!("{:.2e}", availability.simulate_not_live(&actual_distribution, n, availability_iterations)),
+            ]);
+        }
+    }
+    print!("{}", table.to_terminal());
+    println!();
+    println!("Probabilities of samples being safe and live if the adversary can choose the best of k");
+    println!("independently drawn samples (e.g., by biasing the randomness used for sampling).");
+    println!("Simulation only for k <= {}.", max_grinding_sim_draws);
+    println!();
+    let mut table = Table::new(&["n", "f", "k", "not safe", "not live", "type"]);
+    for (n, f_num, f_denom) in [(111, 1, 5), (111, 1, 3)] {
+        let f = f_num as f64 / f_denom as f64;
+        let not_safe = failure_probability(n, f_num, f_denom, 2.0/3.0).to_f64().unwrap();
+        let not_live = failure_probability(n, f_num, f_denom, 1.0/3.0).to_f64().unwrap();
+        for k in [1, 2, 4, 16, 256, 4096] {
+            table.add_row(vec![
+                n.to_string(),
+                format!("{}/{}", f_num, f_denom),
+                k.to_string(),
+                format!("{:.2e}", grinding_failure_probability(not_safe, k)),
+                format!("{:.2e}", grinding_failure_probability(not_live, k)),
+                "Calc".to_owned(),
+            ]);
+            if k as usize <= max_grinding_sim_draws {
+                table.add_row(vec![
+                    n.to_string(),
+                    format!("{}/{}", f_num, f_denom),
+                    k.to_string(),
+                    format!("{:.2e}", simulate_grinding_prob(n as u64, f, 2.0/3.0, k as usize, sim_iterations)),
+                    format!("{:.2e}", simulate_grinding_prob(n as u64, f, 1.0/3.0, k as usize, sim_iterations)),
+                    "Sim".to_owned(),
+                ]);
+            }
+        }
+    }
+    print!("{}", table.to_terminal());
+
+    println!();
+    println!("Probabilities of samples being safe and live with Algorand-style sortition, where each unit of");
+    println!("stake is selected independently, such that the sample size is random with mean tau.");
+    println!("A sample is not safe / not live if at least 2/3 / 1/3 of its actual seats are faulty.");
+    println!("Fixed: fixed sample size tau (Calc). Sort.: sortition (Calc in the limit of infinitely divisible");
+    println!("stake, Sim on the actual distribution with {} stake units and {} iterations).", sortition_stake_units, sortition_sim_iterations);
+    println!();
+    let mut table = Table::new(&["tau", "f", "avg size", "not safe", "not live", "type"]);
+    for (tau, f_num, f_denom) in [(111, 1, 5), (111, 1, 3), (141, 1, 3)] {
+        let f = f_num as f64 / f_denom as f64;
+        let result = simulate_sortition(&actual_distribution, tau as f64, f, sortition_stake_units, sortition_sim_iterations);
+        table.add_row(vec![
+            tau.to_string(),
+            format!("{}/{}", f_num, f_denom),
+            tau.to_string(),
+            format!("{:.2e}", failure_probability(tau, f_num, f_denom, 2.0/3.0).to_f64().unwrap()),
+            format!("{:.2e}", failure_probability(tau, f_num, f_denom, 1.0/3.0).to_f64().unwrap()),
+            "Fixed (Calc)".to_owned(),
+        ]);
+        table.add_row(vec![
+            tau.to_string(),
+            format!("{}/{}", f_num, f_denom),
+            tau.to_string(),
+            format!("{:.2e}", sortition_failure_probability(tau as f64, f, 2.0/3.0, Property::Safety)),
+            format!("{:.2e}", sortition_failure_probability(tau as f64, f, 1.0/3.0, Property::Liveness)),
+            "Sort. (Calc)".to_owned(),
+        ]);
+        table.add_row(vec![
+            tau.to_string(),
+            format!("{}/{}", f_num, f_denom),
+            format!("{:.2}", result.avg_size),
+            format!("{:.2e}", result.not_safe),
+            format!("{:.2e}", result.not_live),
+            "Sort. (Sim)".to_owned(),
+        ]);
+    }
+    print!("{}", table.to_terminal());
+
+    println!();
+    println!("Probabilities of samples from the actual distribution not being safe / live for different vote");
+    println!("weights within the sample. Each node is faulty independently with probability f. A sample is not");
+    println!("safe / not live if the faulty validators hold at least 2/3 / 1/3 of the sample's votes.");
+    println!("Simulated with {} iterations.", vote_weight_sim_iterations);
+    println!();
+    let mut table = Table::new(&["n", "f", "votes", "not safe", "not live"]);
+    for (n, f_num, f_denom) in [(111, 1, 5), (111, 1, 3)] {
+        for vote_weight in [VoteWeight::Seat, VoteWeight::Node, VoteWeight::Stake] {
+            let f = f_num as f64 / f_denom as f64;
+            table.add_row(vec![
+                n.to_string(),
+                format!("{}/{}", f_num, f_denom),
+                vote_weight.name().to_owned(),
+                format!("{:.2e}", simulate_vote_weight_prob(&actual_distribution, n, Selection::Random, f, vote_weight, 2.0/3.0, vote_weight_sim_iterations)),
+                format!("{:.2e}", simulate_vote_weight_prob(&actual_distribution, n, Selection::Random, f, vote_weight, 1.0/3.0, vote_weight_sim_iterations)),
+            ]);
+        }
+    }
+    print!("{}", table.to_terminal());
+
+    println!();
+    println!("Probabilities of samples from the actual distribution not being safe / live with and without");
+    println!("capping policies (see the sample strength section). Each node is faulty independently with");
+    println!("probability f. Simulated with {} iterations.", vote_weight_sim_iterations);
+    println!();
+    let mut table = Table::new(&["n", "f", "policy", "not safe", "not live"]);
+    for (n, f_num, f_denom) in [(111, 1, 5), (111, 1, 3)] {
+        let f = f_num as f64 / f_denom as f64;
+        let f_string = format!("{}/{}", f_num, f_denom);
+        for selection in [Selection::Random, Selection::SeatCap(1), Selection::SeatCap(2)] {
+            table.add_row(vec![
+                n.to_string(),
+                f_string.clone(),
+                selection.name(),
+                format!("{:.2e}", simulate_vote_weight_prob(&actual_distribution, n, selection, f, VoteWeight::Seat, 2.0/3.0, vote_weight_sim_iterations)),
+                format!("{:.2e}", simulate_vote_weight_prob(&actual_distribution, n, selection, f, VoteWeight::Seat, 1.0/3.0, vote_weight_sim_iterations)),
+            ]);
+        }
+        for cap in [0.01, 0.02] {
+            let capped_distribution = CappedStakeDistribution::new(&actual_distribution, cap);
+            table.add_row(vec![
+                n.to_string(),
+                f_string.clone(),
+                format!("stake cap {}", cap),
+                format!("{:.2e}", simulate_vote_weight_prob(&capped_distribution, n, Selection::Random, f, VoteWeight::Seat, 2.0/3.0, vote_weight_sim_iterations)),
+                format!("{:.2e}", simulate_vote_weight_prob(&capped_distribution, n, Selection::Random, f, VoteWeight::Seat, 1.0/3.0, vote_weight_sim_iterations)),
+            ]);
+        }
+    }
+    print!("{}", table.to_terminal());
+
+    println!();
+    println!("Probabilities of samples from the actual distribution not being safe / live if the top k nodes");
+    println!("by stake always get one seat each and the remaining seats are sampled from the other nodes.");
+    println!("The adversary (fraction f of the stake) owns t of the top k nodes (the smallest ones) and the");
+    println!("rest of its stake is spread over the other nodes; t is chosen to maximize the probability.");
+    println!("Calc: each random seat is faulty independently. Sim: each other node is faulty independently");
+    println!("(for the t maximizing the Calc probability, {} iterations).", top_k_sim_iterations);
+    println!();
+    let mut table = Table::new(&["n", "k", "f", "t", "not safe", "t", "not live", "not live (Sim)"]);
+    for (f_num, f_denom) in [(1, 5), (1, 3)] {
+        let f = f_num as f64 / f_denom as f64;
+        for k in [0, 5, 10, 20, 40] {
+            let n = 111;
+            let (safety_t, not_safe) = top_k_failure_probability(&actual_distribution, n, k, f, 2.0/3.0);
+            let (liveness_t, not_live) = top_k_failure_probability(&actual_distribution, n, k, f, 1.0/3.0);
+            table.add_row(vec![
+                n.to_string(),
+                k.to_string(),
+                format!("{}/{}", f_num, f_denom),
+                safety_t.to_string(),
+                format!("{:.2e}", not_safe),
+                liveness_t.to_string(),
+                format!("{:.2e}", not_live),
+                format!("{:.2e}", simulate_top_k_prob(&actual_distribution, n, k, liveness_t, f, 1.0/3.0, top_k_sim_iterations)),
+            ]);
+        }
+    }
+    print!("{}", table.to_terminal());
+
+    println!();
+    println!("Joint probabilities of samples being safe and live. The number of faulty seats falls into one of");
+    println!("three regions: safe and live (fewer than 1/3 faulty), not live only (at least 1/3, but fewer");
+    println!("than 2/3 faulty) and not safe (at least 2/3 faulty, also not live).");
+    println!("Seat: each seat is faulty independently with probability f (Calc exact, Sim {} iterations).", sim_iterations);
+    println!("Node: samples from the actual distribution, each node faulty independently with probability f");
+    println!("(Calc: exact probabilities of each sample, averaged over {} random samples; Sim: {} iterations).", joint_node_iterations, joint_node_iterations);
+    println!();
+    let mut table = Table::new(&["n", "f", "safe and live", "not live only", "not safe", "type"]);
+    for (n, f_num, f_denom) in [(141, 1, 3), (111, 1, 3), (111, 1, 5), (90, 1, 5)] {
+        let f = f_num as f64 / f_denom as f64;
+        let exact = joint_probabilities(n, f_num, f_denom);
+        let rows = [
+            (exact.correct.to_f64().unwrap(), exact.not_live_only.to_f64().unwrap(), exact.not_safe.to_f64().unwrap(), "Seat Calc"),
+            {
+                let r = simulate_joint(n, f, sim_iterations);
+                (r.correct, r.not_live_only, r.not_safe, "Seat Sim")
+            },
+            {
+                let r = expected_node_joint_probabilities(&actual_distribution, n, f, joint_node_iterations);
+                (r.correct, r.not_live_only, r.not_safe, "Node Calc")
+            },
+            {
+                let r = simulate_node_joint(&actual_distribution, n, f, joint_node_iterations);
+                (r.correct, r.not_live_only, r.not_safe, "Node Sim")
+            },
+        ];
+        for (correct, not_live_only, not_safe, kind) in rows {
+            table.add_row(vec![
+                n.to_string(),
+                format!("{}/{}", f_num, f_denom),
+                format!("{:.6}", correct),
+                format!("{:.2e}", not_live_only),
+                format!("{:.2e}", not_safe),
+                kind.to_owned(),
+            ]);
+        }
+    }
+    print!("{}", table.to_terminal());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::rational;
+
+    #[test]
+    fn entity_fails_as_a_whole() {
+        // Both nodes are run by the same operator, so either all seats are faulty or none.
+        let distribution = CustomStakeDistribution::from_csv("val-a,1,op-1\nval-b,1,op-1\n");
+        let grouping = EntityGrouping::from_operators(&distribution);
+        let per_node = EntityGrouping::per_node(&distribution);
+        assert_eq!(grouping.num_entities(), 1);
+        assert!((expected_entity_failure_probability(&distribution, &grouping, 9, 0.25, 1.0/3.0, 10) - 0.25).abs() < 1e-12);
+        assert!(expected_entity_failure_probability(&distribution, &per_node, 9, 0.25, 1.0/3.0, 10) > 0.25);
+    }
+
+    #[test]
+    fn max_faulty_fraction_inverts_failure_probability() {
+        let f = max_faulty_fraction(111, 2.0/3.0, 1.9e-13, 12).unwrap();
+        let step = rational(1, 1 << 12);
+        assert!(failure_probability_for_fraction(111, &f, 2.0/3.0).to_f64().unwrap() < 1.9e-13);
+        assert!(failure_probability_for_fraction(111, &(f + step), 2.0/3.0).to_f64().unwrap() >= 1.9e-13);
+        // A sample of size 111 with f = 1/3 is not safe with probability 7.71e-13 (see compute()),
+        // which is below 1e-12.
+        assert!(max_faulty_fraction(111, 2.0/3.0, 1e-12, 12).unwrap() > rational(1, 3));
+    }
+
+    #[test]
+    fn poisson_binomial_with_equal_probabilities_is_binomial() {
+        let p = rational(1, 3);
+        let trials = vec![(p.clone(), 1); 20];
+        let pmf = poisson_binomial_pmf(&trials);
+        assert_eq!(pmf.len(), 21);
+        for (k, mass) in pmf.iter().enumerate() {
+            assert_eq!(*mass, bin_probability_mass(20, &p, k as u32));
+        }
+        assert_eq!(poisson_binomial_cdf(&trials, 7), bin_probability_cdf(20, &p, 7));
+        assert_eq!(poisson_binomial_tail(&trials)[8], BigRational::one() - bin_probability_cdf(20, &p, 7));
+        // Unlike 1 - cdf, the tail keeps tiny probabilities in floating point arithmetic.
+        assert!((poisson_binomial_tail(&[(1e-10_f64, 1); 3])[3] - 1e-30).abs() < 1e-40);
+    }
+
+    #[test]
+    fn poisson_binomial_with_different_probabilities() {
+        let pmf = poisson_binomial_pmf(&[(rational(1, 2), 1), (rational(1, 4), 1)]);
+        assert_eq!(pmf, vec![rational(3, 8), rational(4, 8), rational(1, 8)]);
+
+        let weighted = poisson_binomial_pmf(&[(0.5, 2), (0.25, 1)]);
+        assert_eq!(weighted, vec![0.375, 0.125, 0.375, 0.125]);
+    }
+}
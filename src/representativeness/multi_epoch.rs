@@ -0,0 +1,66 @@
+use rand::Rng;
+use crate::strength::churn::ChurnModel;
+use crate::strength::stake_distribution::CustomStakeDistribution;
+use crate::strength::validator_sample::ValidatorSample;
+
+// MultiEpochSimulation represents multiple runs of a system over a horizon of several epochs.
+// In each run, the stake starts from a given initial distribution and evolves between epochs
+// according to a churn model, with a new validator sample being selected in every epoch.
+// Each node (including nodes that join later) is faulty independently with probability f.
+// A sample is not safe if at least 2/3 of its seats are faulty and not live if at least 1/3 are.
+pub struct MultiEpochSimulation<'a, C: ChurnModel> {
+    initial_distribution: &'a CustomStakeDistribution,
+    churn_model: &'a C,
+
+    // For each run, whether some epoch had a sample that was not safe / not live.
+    unsafe_runs: Vec<bool>,
+    unlive_runs: Vec<bool>,
+}
+
+impl<'a, C: ChurnModel> MultiEpochSimulation<'a, C> {
+    pub fn new(initial_distribution: &'a CustomStakeDistribution, churn_model: &'a C) -> Self {
+        Self {
+            initial_distribution,
+            churn_model,
+            unsafe_runs: vec![],
+            unlive_runs: vec![],
+        }
+    }
+
+    pub fn run(&mut self, sample_size: usize, epochs: usize, f: f64, iterations: usize) {
+        let mut rng = rand::rng();
+        let unsafe_faults = (2 * sample_size).div_ceil(3);
+        let unlive_faults = sample_size.div_ceil(3);
+
+        for _ in 0..iterations {
+            let mut stakes = self.initial_distribution.stakes().to_vec();
+            let mut faulty: Vec<bool> = stakes.iter().map(|_| rng.random_bool(f)).collect();
+            let mut not_safe = false;
+            let mut not_live = false;
+            for _ in 0..epochs {
+                self.churn_model.evolve(&mut stakes, &mut rng);
+                while faulty.len() < stakes.len() {
+                    faulty.push(rng.random_bool(f));
+                }
+
+                let distribution = CustomStakeDistribution::from_stakes(stakes.clone());
+                let sample = ValidatorSample::new(sample_size, &distribution);
+                let faults = sample.nodes().iter().filter(|&&n| faulty[n]).count();
+                not_safe |= faults >= unsafe_faults;
+                not_live |= faults >= unlive_faults;
+            }
+            self.unsafe_runs.push(not_safe);
+            self.unlive_runs.push(not_live);
+        }
+    }
+
+    // Returns the fraction of runs in which at least one epoch had a sample that was not safe.
+    pub fn not_safe_probability(&self) -> f64 {
+        self.unsafe_runs.iter().filter(|&&r| r).count() as f64 / self.unsafe_runs.len() as f64
+    }
+
+    // Returns the fraction of runs in which at least one epoch had a sample that was not live.
+    pub fn not_live_probability(&self) -> f64 {
+        self.unlive_runs.iter().filter(|&&r| r).count() as f64 / self.unlive_runs.len() as f64
+    }
+}
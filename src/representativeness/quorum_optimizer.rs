@@ -0,0 +1,70 @@
+use crate::representativeness::poisson_binomial_tail;
+
+// A sample of sample_size seats, out of which a quorum of quorum_size seats is required to make
+// progress, must have intersecting quorums, i.e., quorum_size > sample_size / 2. Any two quorums
+// then have at least 2 * quorum_size - sample_size seats in common and the sample is
+// - not safe if the faulty seats can cover such an intersection (at least
+//   2 * quorum_size - sample_size faulty seats), so that two conflicting quorums may form, and
+// - not live if the correct seats alone do not form a quorum (more than sample_size - quorum_size
+//   faulty seats).
+// Each seat is faulty independently with probability f.
+pub struct QuorumChoice {
+    pub sample_size: usize,
+    pub quorum_size: usize,
+    pub not_safe: f64,
+    pub not_live: f64,
+}
+
+// Returns, for each sample size up to max_sample_size, the smallest quorum size for which the
+// probabilities of the sample not being safe and not being live are lower than the respective
+// targets, keeping only the Pareto-optimal choices: those for which no smaller sample admits a
+// quorum that is at most the same fraction of the sample. I.e., a larger sample is only worth it
+// if it allows a smaller fraction of the seats to form a quorum (so that more correct validators
+// may be unresponsive). The result is sorted by sample size (and so by decreasing quorum fraction).
+pub fn pareto_quorums(f: f64, safety_target: f64, liveness_target: f64, max_sample_size: usize) -> Vec<QuorumChoice> {
+    let mut choices: Vec<QuorumChoice> = vec![];
+    for n in 1..=max_sample_size {
+        // tail[k] is the probability of at least k faulty seats.
+        let tail = poisson_binomial_tail(&vec![(f, 1); n]);
+
+        // Quorums of at most half of the seats do not intersect.
+        let choice = (n / 2 + 1..=n)
+            .map(|q| QuorumChoice { sample_size: n, quorum_size: q, not_safe: tail[2 * q - n], not_live: tail[n - q + 1] })
+            .find(|c| c.not_safe < safety_target && c.not_live < liveness_target);
+        if let Some(choice) = choice {
+            let dominated = choices.last().is_some_and(|best| {
+                // Compare the fractions q / n exactly.
+                best.quorum_size * choice.sample_size <= choice.quorum_size * best.sample_size
+            });
+            if !dominated {
+                choices.push(choice);
+            }
+        }
+    }
+
+    choices
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pareto_quorums_without_faults() {
+        // Without faulty seats, a single common seat keeps quorums safe, so any majority quorum is
+        // safe and live, and majorities of odd-sized samples are the smallest fractions.
+        let choices = pareto_quorums(0.0, 1e-9, 1e-9, 5);
+        let pairs: Vec<(usize, usize)> = choices.iter().map(|c| (c.sample_size, c.quorum_size)).collect();
+        assert_eq!(pairs, vec![(1, 1), (3, 2), (5, 3)]);
+    }
+
+    #[test]
+    fn pareto_quorums_with_faults() {
+        // E.g., n = 3 and q = 2 is not safe if the single common seat of two quorums is faulty
+        // (probability 0.271), while n = 5 and q = 4 requires 3 faulty seats (probability 0.00856).
+        let choices = pareto_quorums(0.1, 0.05, 0.3, 10);
+        let pairs: Vec<(usize, usize)> = choices.iter().map(|c| (c.sample_size, c.quorum_size)).collect();
+        assert_eq!(pairs, vec![(2, 2), (5, 4), (7, 5), (10, 7)]);
+        assert!((choices[1].not_safe - 0.00856).abs() < 1e-12);
+    }
+}
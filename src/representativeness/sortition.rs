@@ -0,0 +1,124 @@
+use crate::representativeness::sybil::{SplitStakeDistribution, SplittingStrategy};
+use crate::strength::sortition::SortitionSampler;
+use crate::strength::stake_distribution::StakeDistribution;
+
+// With sortition (see SortitionSampler), the numbers of faulty (B) and correct (H) seats are
+// independent and, for a large number of stake units, Poisson-distributed with means f * tau and
+// (1 - f) * tau, where tau is the expected sample size and f the fraction of faulty stake.
+// Since the sample size B + H is random, the fault threshold applies to the actual sample size:
+// a sample fails if B >= fault_threshold * (B + H). An empty sample is not live, but it is safe,
+// so whether it fails depends on the property the threshold protects.
+
+// The property protected by a fault threshold.
+#[derive(Clone, Copy, Debug)]
+pub enum Property {
+    Safety,
+    Liveness,
+}
+
+// Returns the Poisson probability mass function with mean lambda for 0, 1, ..., max_k.
+// Computed in the log domain to stay precise in the tails.
+fn poisson_pmf(lambda: f64, max_k: usize) -> Vec<f64> {
+    if lambda == 0.0 {
+        return (0..=max_k).map(|k| if k == 0 { 1.0 } else { 0.0 }).collect();
+    }
+    let mut ln_factorial = 0.0;
+    (0..=max_k).map(|k| {
+        if k > 0 {
+            ln_factorial += (k as f64).ln();
+        }
+        ((k as f64) * lambda.ln() - lambda - ln_factorial).exp()
+    }).collect()
+}
+
+fn is_failure(faulty: usize, correct: usize, fault_threshold: f64, property: Property) -> bool {
+    if faulty + correct == 0 {
+        return matches!(property, Property::Liveness);
+    }
+    faulty as f64 >= fault_threshold * (faulty + correct) as f64
+}
+
+// Returns the probability that a sample selected by sortition with expected size tau contains a
+// fraction of at least fault_threshold faulty seats (or is empty, if the threshold protects
+// liveness), if a fraction f of the stake is faulty.
+// Uses the Poisson limit (i.e., an infinitely divisible stake).
+pub fn sortition_failure_probability(tau: f64, f: f64, fault_threshold: f64, property: Property) -> f64 {
+    // Beyond this many seats, the Poisson tails are negligible even for the smallest probabilities.
+    let max_seats = (tau + 40.0 * tau.sqrt() + 100.0).ceil() as usize;
+    let faulty_pmf = poisson_pmf(f * tau, max_seats);
+    let correct_pmf = poisson_pmf((1.0 - f) * tau, max_seats);
+
+    let mut result = 0.0;
+    for (b, faulty_mass) in faulty_pmf.iter().enumerate() {
+        let failing_mass: f64 = correct_pmf.iter().enumerate()
+            .filter(|&(h, _)| is_failure(b, h, fault_threshold, property))
+            .map(|(_, mass)| mass)
+            .sum();
+        result += faulty_mass * failing_mass;
+    }
+
+    result
+}
+
+// Results of sampling by sortition. Probabilities are relative frequencies.
+pub struct SortitionSimulationResult {
+    pub avg_size: f64,
+    pub not_safe: f64,
+    pub not_live: f64,
+}
+
+// Repeatedly selects samples by sortition from the given distribution, to which an adversary
+// holding a fraction f of the stake is added, and measures how often the sample is not safe
+// (at least 2/3 faulty seats) or not live (at least 1/3 faulty seats).
+pub fn simulate_sortition<D: StakeDistribution>(
+    honest: &D,
+    tau: f64,
+    f: f64,
+    stake_units: u64,
+    iterations: usize,
+) -> SortitionSimulationResult {
+    let distribution = SplitStakeDistribution::new(honest, f, SplittingStrategy::Single);
+    let sampler = SortitionSampler::new(&distribution, tau, stake_units);
+    let mut total_size = 0;
+    let mut not_safe = 0;
+    let mut not_live = 0;
+    for _ in 0..iterations {
+        let sample = sampler.sample();
+        let size = sample.nodes().len();
+        let faulty = sample.nodes().iter().filter(|&&n| distribution.is_adversarial(n)).count();
+        total_size += size;
+        if is_failure(faulty, size - faulty, 2.0/3.0, Property::Safety) {
+            not_safe += 1;
+        }
+        if is_failure(faulty, size - faulty, 1.0/3.0, Property::Liveness) {
+            not_live += 1;
+        }
+    }
+
+    SortitionSimulationResult {
+        avg_size: total_size as f64 / iterations as f64,
+        not_safe: not_safe as f64 / iterations as f64,
+        not_live: not_live as f64 / iterations as f64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sortition_failure_probability_bounds() {
+        // Without faulty stake, a non-empty sample never fails and an empty one only fails liveness,
+        // whatever the threshold.
+        let empty = (-10.0f64).exp();
+        assert!((sortition_failure_probability(10.0, 0.0, 1.0/3.0, Property::Liveness) - empty).abs() < 1e-15);
+        assert!((sortition_failure_probability(10.0, 0.0, 0.6, Property::Liveness) - empty).abs() < 1e-15);
+        assert_eq!(sortition_failure_probability(10.0, 0.0, 2.0/3.0, Property::Safety), 0.0);
+        assert_eq!(sortition_failure_probability(10.0, 0.0, 0.4, Property::Safety), 0.0);
+        // With only faulty stake, every non-empty sample fails.
+        assert!((sortition_failure_probability(10.0, 1.0, 2.0/3.0, Property::Safety) - (1.0 - empty)).abs() < 1e-12);
+        // Failing safety is much less likely than failing liveness.
+        assert!(sortition_failure_probability(100.0, 0.2, 2.0/3.0, Property::Safety)
+            < sortition_failure_probability(100.0, 0.2, 1.0/3.0, Property::Liveness));
+    }
+}
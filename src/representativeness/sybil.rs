@@ -0,0 +1,164 @@
+use rand::Rng;
+use crate::strength::stake_distribution::StakeDistribution;
+use crate::strength::validator_sample::ValidatorSample;
+
+// How an adversary splits its stake across multiple identities (nodes).
+#[derive(Clone, Copy, Debug)]
+pub enum SplittingStrategy {
+    // All adversarial stake in a single identity.
+    Single,
+    // The adversarial stake split equally among the given number of identities.
+    Equal(usize),
+    // The adversarial stake split among the given number of identities, such that each identity
+    // has ratio times the stake of the previous one.
+    Geometric { identities: usize, ratio: f64 },
+}
+
+impl SplittingStrategy {
+    // Returns the fraction of the adversarial stake assigned to each adversarial identity.
+    pub fn shares(&self) -> Vec<f64> {
+        let weights: Vec<f64> = match *self {
+            SplittingStrategy::Single => vec![1.0],
+            SplittingStrategy::Equal(identities) => vec![1.0; identities],
+            SplittingStrategy::Geometric { identities, ratio } => (0..identities).map(|i| ratio.powi(i as i32)).collect(),
+        };
+        let total: f64 = weights.iter().sum();
+        weights.iter().map(|w| w / total).collect()
+    }
+
+    pub fn name(&self) -> String {
+        match *self {
+            SplittingStrategy::Single => "single".to_owned(),
+            SplittingStrategy::Equal(identities) => format!("{} equal", identities),
+            SplittingStrategy::Geometric { identities, ratio } => format!("{} geom. {}", identities, ratio),
+        }
+    }
+}
+
+// A SplitStakeDistribution adds an adversary to an underlying distribution of honest nodes.
+// The adversary holds a fraction adversary_fraction of the total stake, split across multiple
+// identities according to a splitting strategy. The honest nodes keep their relative stakes and
+// hold the remaining stake. Honest nodes keep their node IDs, adversarial identities come after them.
+pub struct SplitStakeDistribution<'a, D: StakeDistribution> {
+    honest: &'a D,
+    adversary_fraction: f64,
+    shares: Vec<f64>,
+}
+
+impl<'a, D: StakeDistribution> SplitStakeDistribution<'a, D> {
+    pub fn new(honest: &'a D, adversary_fraction: f64, strategy: SplittingStrategy) -> Self {
+        Self { honest, adversary_fraction, shares: strategy.shares() }
+    }
+
+    pub fn is_adversarial(&self, node: usize) -> bool {
+        node >= self.honest.num_nodes() && node < self.num_nodes()
+    }
+}
+
+impl<D: StakeDistribution> StakeDistribution for SplitStakeDistribution<'_, D> {
+    fn stake_of(&self, node: usize) -> f64 {
+        if node < self.honest.num_nodes() {
+            (1.0 - self.adversary_fraction) * self.honest.stake_of(node)
+        } else {
+            self.shares.get(node - self.honest.num_nodes()).map_or(0.0, |s| self.adversary_fraction * s)
+        }
+    }
+
+    fn num_nodes(&self) -> usize {
+        self.honest.num_nodes() + self.shares.len()
+    }
+
+    // The honest nodes divide their share of the units according to the underlying distribution,
+    // so that its (possibly exact) stakes are preserved.
+    fn stake_units(&self, node: usize, total_units: u64) -> u64 {
+        if node < self.honest.num_nodes() {
+            let honest_units = ((1.0 - self.adversary_fraction) * total_units as f64).round() as u64;
+            self.honest.stake_units(node, honest_units)
+        } else {
+            (self.stake_of(node) * total_units as f64).round() as u64
+        }
+    }
+
+    // First decides between the adversary and the honest nodes and then samples among those,
+    // so that the underlying distribution's (possibly exact) sampling is preserved.
+    fn sample(&self) -> usize {
+        let mut rng = rand::rng();
+        if !rng.random_bool(self.adversary_fraction) {
+            return self.honest.sample();
+        }
+        let random_share: f64 = rng.random();
+        let mut sum = 0.0;
+        for (i, share) in self.shares.iter().enumerate() {
+            sum += share;
+            if sum > random_share {
+                return self.honest.num_nodes() + i;
+            }
+        }
+        self.num_nodes() - 1 // Only reached due to rounding errors.
+    }
+}
+
+// Results of sampling from a SplitStakeDistribution. Probabilities are relative frequencies.
+pub struct SplitSimulationResult {
+    pub avg_adversarial_seats: f64,
+    pub avg_adversarial_identities: f64,
+    pub not_safe: f64,
+    pub not_live: f64,
+}
+
+// Repeatedly samples validators from a SplitStakeDistribution and measures how many seats the
+// adversary obtains and how often the sample is not safe (at least 2/3 adversarial seats)
+// or not live (at least 1/3 adversarial seats).
+pub fn simulate_split<D: StakeDistribution>(
+    distribution: &SplitStakeDistribution<D>,
+    sample_size: usize,
+    iterations: usize,
+) -> SplitSimulationResult {
+    let mut seats = 0;
+    let mut identities = 0;
+    let mut not_safe = 0;
+    let mut not_live = 0;
+    for _ in 0..iterations {
+        let sample = ValidatorSample::new(sample_size, distribution);
+        let adversarial: Vec<(usize, usize)> = sample.seats_by_node().into_iter()
+            .filter(|&(n, _)| distribution.is_adversarial(n))
+            .collect();
+        let adversarial_seats: usize = adversarial.iter().map(|(_, s)| s).sum();
+        seats += adversarial_seats;
+        identities += adversarial.len();
+        if adversarial_seats >= (2 * sample_size).div_ceil(3) {
+            not_safe += 1;
+        }
+        if adversarial_seats >= sample_size.div_ceil(3) {
+            not_live += 1;
+        }
+    }
+
+    SplitSimulationResult {
+        avg_adversarial_seats: seats as f64 / iterations as f64,
+        avg_adversarial_identities: identities as f64 / iterations as f64,
+        not_safe: not_safe as f64 / iterations as f64,
+        not_live: not_live as f64 / iterations as f64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strength::stake_distribution::UniformStakeDistribution;
+    use crate::test_util::approx_equal;
+
+    #[test]
+    fn split_distribution_stakes() {
+        let honest = UniformStakeDistribution::new(4);
+        let distribution = SplitStakeDistribution::new(&honest, 0.2, SplittingStrategy::Geometric { identities: 2, ratio: 0.25 });
+        assert_eq!(distribution.num_nodes(), 6);
+        assert!(approx_equal(distribution.stake_of(0), 0.2));
+        assert!(approx_equal(distribution.stake_of(4), 0.16));
+        assert!(approx_equal(distribution.stake_of(5), 0.04));
+        assert!(!distribution.is_adversarial(3));
+        assert!(distribution.is_adversarial(5));
+        let total: f64 = (0..distribution.num_nodes()).map(|n| distribution.stake_of(n)).sum();
+        assert!(approx_equal(total, 1.0));
+    }
+}
@@ -0,0 +1,121 @@
+use rand::Rng;
+use crate::representativeness::poisson_binomial_tail;
+use crate::strength::stake_distribution::StakeDistribution;
+use crate::strength::validator_sample::{top_nodes, Selection, ValidatorSample};
+
+// With top-k-plus-random selection (see Selection::TopKPlusRandom), the top k nodes always have
+// one seat each, so an adversary can deliberately own some of them. An adversary holding a fraction
+// f of the total stake and owning t of the top k nodes owns the t smallest of them (maximizing
+// its remaining stake) and the rest of its stake is spread over the other nodes. Each of the n - k
+// random seats is then faulty with probability (f - stake of the t nodes) / (stake of the other
+// nodes). We consider the worst case over all t the adversary can afford.
+
+// Returns the fraction of the stake of the non-top nodes held by the adversary, if it owns t of the
+// top k nodes, or None if the adversary cannot afford them. If the non-top nodes have no stake,
+// none of them can be faulty (and no random seats can be filled, see Selection::TopKPlusRandom).
+fn remaining_fault_fraction<D: StakeDistribution>(distribution: &D, k: usize, t: usize, f: f64) -> Option<f64> {
+    if k > distribution.num_nodes() {
+        panic!("Cannot take the top {} of {} nodes", k, distribution.num_nodes());
+    }
+    let top = top_nodes(distribution, k);
+    let owned_stake: f64 = top.iter().rev().take(t).map(|&n| distribution.stake_of(n)).sum();
+    if owned_stake > f {
+        return None;
+    }
+    let other_stake: f64 = (0..distribution.num_nodes())
+        .filter(|n| !top.contains(n))
+        .map(|n| distribution.stake_of(n))
+        .sum();
+    if other_stake <= 0.0 {
+        return Some(0.0);
+    }
+    Some(((f - owned_stake) / other_stake).min(1.0))
+}
+
+// Returns the highest probability, over the number t of top nodes owned by the adversary, of a
+// sample of size n with the top k nodes containing a fraction of at least fault_threshold faulty
+// seats, together with the maximizing t. Each random seat is faulty independently.
+pub fn top_k_failure_probability<D: StakeDistribution>(
+    distribution: &D,
+    n: usize,
+    k: usize,
+    f: f64,
+    fault_threshold: f64,
+) -> (usize, f64) {
+    if k > n {
+        panic!("Cannot include the top {} nodes in a sample of size {}", k, n);
+    }
+    let min_faults = ((n as f64) * fault_threshold).ceil() as usize;
+    (0..=k)
+        .filter_map(|t| remaining_fault_fraction(distribution, k, t, f).map(|p| (t, p)))
+        .map(|(t, p)| {
+            let tail = poisson_binomial_tail(&vec![(p, 1); n - k]);
+            (t, tail.get(min_faults.saturating_sub(t)).copied().unwrap_or(0.0))
+        })
+        .fold((0, 0.0), |best, candidate| if candidate.1 > best.1 { candidate } else { best })
+}
+
+// Repeatedly samples validators using top-k-plus-random selection and returns how many times
+// (relative to the number of iterations) the faulty validators held a fraction of at least
+// fault_threshold of the seats. The adversary owns t of the top k nodes as described above and each
+// other node is faulty independently with probability equal to the adversary's remaining fraction.
+pub fn simulate_top_k_prob<D: StakeDistribution>(
+    distribution: &D,
+    n: usize,
+    k: usize,
+    t: usize,
+    f: f64,
+    fault_threshold: f64,
+    iterations: usize,
+) -> f64 {
+    let mut rng = rand::rng();
+    let p = remaining_fault_fraction(distribution, k, t, f)
+        .unwrap_or_else(|| panic!("Adversary with stake {} cannot own {} of the top {} nodes", f, t, k));
+    let top = top_nodes(distribution, k);
+    let owned: Vec<usize> = top.iter().rev().take(t).copied().collect();
+    let min_faults = ((n as f64) * fault_threshold).ceil() as usize;
+
+    let mut failures = 0;
+    for _ in 0..iterations {
+        let sample = ValidatorSample::select(n, distribution, Selection::TopKPlusRandom(k));
+        let faults: usize = sample.seats_by_node().into_iter()
+            .filter(|&(node, _)| if top.contains(&node) { owned.contains(&node) } else { rng.random_bool(p) })
+            .map(|(_, seats)| seats)
+            .sum();
+        if faults >= min_faults {
+            failures += 1;
+        }
+    }
+
+    (failures as f64) / (iterations as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigInt;
+    use crate::strength::stake_distribution::{CustomStakeDistribution, UniformStakeDistribution};
+
+    #[test]
+    fn top_k_without_top_nodes() {
+        // With k = 0, this is the usual binomial failure probability.
+        let distribution = UniformStakeDistribution::new(100);
+        let (t, probability) = top_k_failure_probability(&distribution, 3, 0, 0.5, 2.0/3.0);
+        assert_eq!(t, 0);
+        assert!((probability - 0.5).abs() < 1e-12);
+    }
+    #[test]
+    fn top_k_with_all_stake() {
+        // The other nodes have no stake, so only the top nodes can be faulty.
+        let stakes = vec![BigInt::from(3), BigInt::from(1), BigInt::from(0), BigInt::from(0)];
+        let distribution = CustomStakeDistribution::from_stakes(stakes);
+        assert_eq!(remaining_fault_fraction(&distribution, 2, 1, 0.3), Some(0.0));
+        assert_eq!(remaining_fault_fraction(&distribution, 2, 2, 0.3), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn top_k_with_too_few_nodes() {
+        top_k_failure_probability(&UniformStakeDistribution::new(2), 3, 3, 0.1, 2.0/3.0);
+    }
+}
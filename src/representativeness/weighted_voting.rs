@@ -0,0 +1,32 @@
+use rand::Rng;
+use crate::strength::stake_distribution::StakeDistribution;
+use crate::strength::validator_sample::{Selection, ValidatorSample, VoteWeight};
+
+// Repeatedly samples validators from a stake distribution and returns how many times (relative to
+// the number of iterations) the faulty validators held a fraction of at least fault_threshold of
+// the sample's votes, where votes are counted according to vote_weight.
+// Each node is faulty independently with probability p. Seats are assigned using the given selection.
+pub fn simulate_vote_weight_prob<D: StakeDistribution>(
+    distribution: &D,
+    n: usize,
+    selection: Selection,
+    p: f64,
+    vote_weight: VoteWeight,
+    fault_threshold: f64,
+    iterations: usize,
+) -> f64 {
+    let mut rng = rand::rng();
+    let mut failures = 0;
+    for _ in 0..iterations {
+        let sample = ValidatorSample::select(n, distribution, selection).with_vote_weight(vote_weight);
+        let faulty: Vec<usize> = sample.seats_by_node().into_iter()
+            .map(|(node, _)| node)
+            .filter(|_| rng.random_bool(p))
+            .collect();
+        if sample.votes(|node| faulty.contains(&node)) >= fault_threshold * sample.total_votes() {
+            failures += 1;
+        }
+    }
+
+    (failures as f64) / (iterations as f64)
+}
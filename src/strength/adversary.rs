@@ -0,0 +1,87 @@
+use crate::strength::stake_distribution::StakeDistribution;
+use crate::strength::validator_sample::ValidatorSample;
+
+// The resources an adversary can spend on corrupting validators.
+#[derive(Clone, Copy, Debug)]
+pub enum CorruptionBudget {
+    // Maximal combined stake (as a fraction of the total stake) of the corrupted nodes.
+    Stake(f64),
+    // Maximal number of corrupted nodes.
+    Nodes(usize),
+}
+
+// An AdaptiveAdversary corrupts validators after a validator sample has been selected and revealed,
+// but before the sample becomes active. The adversary needs time to corrupt nodes: it corrupts
+// corruption_rate nodes per time unit and has delay time units between the selection and the
+// activation of the sample. It always corrupts the cheapest (i.e., lowest-stake) nodes of the
+// sample first, as long as the budget and the time allow it.
+pub struct AdaptiveAdversary {
+    pub budget: CorruptionBudget,
+    pub corruption_rate: f64,
+    pub delay: f64,
+}
+
+impl AdaptiveAdversary {
+    // Returns the number of seats of the sample controlled by the adversary after corruption.
+    pub fn corrupted_seats<D: StakeDistribution>(&self, sample: &ValidatorSample<D>) -> usize {
+        let max_nodes_in_time = (self.corruption_rate * self.delay).floor() as usize;
+        let mut corrupted_stake = 0.0;
+        let mut corrupted_seats = 0;
+        for (i, (node, seats)) in sample.seats_by_node().into_iter().enumerate() {
+            if i >= max_nodes_in_time {
+                break;
+            }
+            corrupted_stake += sample.distribution().stake_of(node);
+            match self.budget {
+                CorruptionBudget::Stake(max_stake) if corrupted_stake > max_stake => break,
+                CorruptionBudget::Nodes(max_nodes) if i >= max_nodes => break,
+                _ => corrupted_seats += seats,
+            }
+        }
+
+        corrupted_seats
+    }
+
+    // Repeatedly samples validators and returns how many times (relative to the number of
+    // iterations) the adversary managed to corrupt a weak and a strong quorum of the sample.
+    pub fn simulate<D: StakeDistribution>(&self, distribution: &D, sample_size: usize, iterations: usize) -> (f64, f64) {
+        let mut weak_quorums = 0;
+        let mut strong_quorums = 0;
+        for _ in 0..iterations {
+            let sample = ValidatorSample::new(sample_size, distribution);
+            let seats = self.corrupted_seats(&sample);
+            if seats >= sample.weak_quorum_size() {
+                weak_quorums += 1;
+            }
+            if seats >= sample.strong_quorum_size() {
+                strong_quorums += 1;
+            }
+        }
+
+        (weak_quorums as f64 / iterations as f64, strong_quorums as f64 / iterations as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strength::stake_distribution::UniformStakeDistribution;
+
+    #[test]
+    fn adaptive_adversary_limits() {
+        let distribution = UniformStakeDistribution::new(1000);
+        let sample = ValidatorSample::new(30, &distribution);
+        let distinct_nodes = sample.seats_by_node().len();
+
+        let unlimited = AdaptiveAdversary { budget: CorruptionBudget::Nodes(1000), corruption_rate: 1.0, delay: 1000.0 };
+        assert_eq!(unlimited.corrupted_seats(&sample), 30);
+
+        let slow = AdaptiveAdversary { budget: CorruptionBudget::Nodes(1000), corruption_rate: 0.5, delay: 10.0 };
+        let expected: usize = sample.seats_by_node().iter().take(5).map(|(_, seats)| seats).sum();
+        assert_eq!(slow.corrupted_seats(&sample), expected);
+
+        let poor = AdaptiveAdversary { budget: CorruptionBudget::Stake(0.0035), corruption_rate: 1.0, delay: 1000.0 };
+        let expected: usize = sample.seats_by_node().iter().take(3.min(distinct_nodes)).map(|(_, seats)| seats).sum();
+        assert_eq!(poor.corrupted_seats(&sample), expected);
+    }
+}
@@ -0,0 +1,88 @@
+use std::fs;
+
+// A PriceFunction determines how much it costs to bribe (i.e., corrupt) a node.
+// Prices are expressed in the same unit as stake, i.e., as fractions of the total stake
+// present in the system, so that bribes are comparable to quorum stakes.
+pub trait PriceFunction {
+    // Returns the price of bribing the given node, which has the given stake.
+    fn price(&self, node: usize, stake: f64) -> f64;
+}
+
+// The price of a node is proportional to its stake.
+pub struct ProportionalPrice {
+    pub price_per_stake: f64,
+}
+
+impl PriceFunction for ProportionalPrice {
+    fn price(&self, _node: usize, stake: f64) -> f64 {
+        self.price_per_stake * stake
+    }
+}
+
+// Bribing a node costs a fixed amount (e.g., the node's operating income or reputation)
+// plus compensation for the fraction of its stake that would get slashed.
+pub struct FixedPlusSlashablePrice {
+    pub fixed: f64,
+    pub slashable_fraction: f64,
+}
+
+impl PriceFunction for FixedPlusSlashablePrice {
+    fn price(&self, _node: usize, stake: f64) -> f64 {
+        self.fixed + self.slashable_fraction * stake
+    }
+}
+
+// Arbitrary per-node prices, loaded from a file containing one number per line, where the k-th
+// line is the price of the k-th node (numbering starting from zero). Nodes without a price
+// cannot be bribed.
+pub struct TablePrice {
+    prices: Vec<f64>,
+}
+
+impl TablePrice {
+    pub fn from_file(file_name: &str) -> Self {
+        let file_content = fs::read_to_string(file_name).unwrap();
+        let prices = file_content.lines()
+            .map(|line| line.trim().parse().unwrap_or_else(|_| panic!("Invalid price value: '{}'", line)))
+            .collect();
+
+        Self { prices }
+    }
+}
+
+impl PriceFunction for TablePrice {
+    fn price(&self, node: usize, _stake: f64) -> f64 {
+        self.prices.get(node).copied().unwrap_or(f64::INFINITY)
+    }
+}
+
+// Returns the minimal total price of a set of nodes that together hold at least quorum_size seats.
+// Each node is given as a pair of its price and its number of seats.
+// Computed exactly by dynamic programming over the number of seats (min-cost covering knapsack).
+pub fn min_bribe(nodes: &[(f64, usize)], quorum_size: usize) -> f64 {
+    // min_cost[s] is the minimal price of obtaining at least s seats.
+    let mut min_cost = vec![f64::INFINITY; quorum_size + 1];
+    min_cost[0] = 0.0;
+    for &(price, seats) in nodes {
+        for s in (1..=quorum_size).rev() {
+            min_cost[s] = min_cost[s].min(min_cost[s.saturating_sub(seats)] + price);
+        }
+    }
+
+    min_cost[quorum_size]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn min_bribe_picks_cheapest_cover() {
+        // The single node with 3 seats is cheaper than the three nodes with one seat each.
+        let nodes = [(1.0, 1), (1.0, 1), (1.0, 1), (2.0, 3), (0.5, 1)];
+        assert_eq!(min_bribe(&nodes, 3), 2.0);
+        assert_eq!(min_bribe(&nodes, 4), 2.5);
+        assert_eq!(min_bribe(&nodes, 0), 0.0);
+        assert_eq!(min_bribe(&nodes, 8), f64::INFINITY);
+    }
+}
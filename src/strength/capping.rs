@@ -0,0 +1,118 @@
+use rand::Rng;
+use crate::strength::stake_distribution::StakeDistribution;
+
+// CappedStakeDistribution models a policy capping the effective stake of each node at a fraction
+// cap of the total effective stake. The effective stake determines the probability of a node being
+// sampled, while stake_of() still returns the node's actual stake, so that quorum stakes
+// refer to the stake actually at risk.
+// The stake exceeding the cap is redistributed to the other nodes in proportion to their stakes
+// (water-filling), possibly making more nodes reach the cap.
+pub struct CappedStakeDistribution<'a, D: StakeDistribution> {
+    distribution: &'a D,
+    cumulative_effective_stakes: Vec<f64>,
+}
+
+impl<'a, D: StakeDistribution> CappedStakeDistribution<'a, D> {
+    pub fn new(distribution: &'a D, cap: f64) -> Self {
+        let num_nodes = distribution.num_nodes();
+        let nodes_with_stake = (0..num_nodes).filter(|&n| distribution.stake_of(n) > 0.0).count();
+        if cap * (nodes_with_stake as f64) < 1.0 {
+            panic!("Stake cap {} too low for {} nodes with stake", cap, nodes_with_stake);
+        }
+
+        let mut capped = vec![false; num_nodes];
+        let mut scale;
+        loop {
+            let num_capped = capped.iter().filter(|&&c| c).count();
+            let uncapped_stake: f64 = (0..num_nodes).filter(|&n| !capped[n]).map(|n| distribution.stake_of(n)).sum();
+            if uncapped_stake == 0.0 {
+                // All nodes with stake are capped (only possible through rounding, if cap is
+                // 1 / nodes_with_stake), so there is no stake left to redistribute.
+                scale = 0.0;
+                break;
+            }
+            scale = (1.0 - num_capped as f64 * cap) / uncapped_stake;
+            let newly_capped: Vec<usize> = (0..num_nodes)
+                .filter(|&n| !capped[n] && distribution.stake_of(n) * scale > cap)
+                .collect();
+            if newly_capped.is_empty() {
+                break;
+            }
+            for n in newly_capped {
+                capped[n] = true;
+            }
+        }
+
+        let mut sum = 0.0;
+        let cumulative_effective_stakes = (0..num_nodes)
+            .map(|n| {
+                sum += if capped[n] { cap } else { distribution.stake_of(n) * scale };
+                sum
+            })
+            .collect();
+
+        Self { distribution, cumulative_effective_stakes }
+    }
+
+    // Returns the effective stake of a node, i.e., the probability of it being sampled.
+    pub fn effective_stake_of(&self, node: usize) -> f64 {
+        match node {
+            0 => self.cumulative_effective_stakes[0],
+            n if n < self.cumulative_effective_stakes.len() => {
+                self.cumulative_effective_stakes[n] - self.cumulative_effective_stakes[n - 1]
+            }
+            _ => 0.0,
+        }
+    }
+}
+
+impl<D: StakeDistribution> StakeDistribution for CappedStakeDistribution<'_, D> {
+    fn stake_of(&self, node: usize) -> f64 {
+        self.distribution.stake_of(node)
+    }
+
+    fn num_nodes(&self) -> usize {
+        self.distribution.num_nodes()
+    }
+
+    fn node_name(&self, node: usize) -> String {
+        self.distribution.node_name(node)
+    }
+
+    // Samples according to the effective (capped) stakes.
+    fn sample(&self) -> usize {
+        let random_stake = rand::rng().random::<f64>() * self.cumulative_effective_stakes.last().unwrap();
+        let node = self.cumulative_effective_stakes.partition_point(|&sum| sum <= random_stake);
+        node.min(self.num_nodes() - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strength::stake_distribution::CustomStakeDistribution;
+
+    #[test]
+    fn water_filling() {
+        // With a cap of 0.3, node 3 (0.6) is capped first, after which node 2 (0.2) would get
+        // 0.7 * 0.2 / 0.4 = 0.35 and is capped too. The rest (0.4) is split 1:1.
+        let distribution = CustomStakeDistribution::from_plain("1\n1\n2\n6\n");
+        let capped = CappedStakeDistribution::new(&distribution, 0.3);
+        let expected = [0.2, 0.2, 0.3, 0.3];
+        for (node, stake) in expected.into_iter().enumerate() {
+            assert!((capped.effective_stake_of(node) - stake).abs() < 1e-12);
+        }
+        assert!((capped.stake_of(3) - 0.6).abs() < 1e-12);
+    }
+
+    #[test]
+    fn cap_with_node_without_stake() {
+        // The node without stake is ignored and both other nodes end up with exactly the cap.
+        let distribution = CustomStakeDistribution::from_plain("1\n0\n3\n");
+        let capped = CappedStakeDistribution::new(&distribution, 0.5);
+        let expected = [0.5, 0.0, 0.5];
+        for (node, stake) in expected.into_iter().enumerate() {
+            assert!((capped.effective_stake_of(node) - stake).abs() < 1e-12);
+        }
+    }
+}
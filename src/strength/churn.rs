@@ -0,0 +1,127 @@
+use num_bigint::BigInt;
+use rand::Rng;
+use rand::rngs::ThreadRng;
+use rand_distr::{Distribution, Poisson};
+use rand_distr::num_traits::{Signed, Zero};
+
+// A ChurnModel describes how the stake evolves from one epoch to the next.
+// Stakes are indexed by node ID. To keep node IDs stable, nodes that leave the system are not
+// removed, but their stake is set to zero. Nodes that join the system are appended at the end.
+pub trait ChurnModel {
+    fn evolve(&self, stakes: &mut Vec<BigInt>, rng: &mut ThreadRng);
+}
+
+// Several churn models applied one after the other.
+impl ChurnModel for Vec<Box<dyn ChurnModel>> {
+    fn evolve(&self, stakes: &mut Vec<BigInt>, rng: &mut ThreadRng) {
+        for model in self {
+            model.evolve(stakes, rng);
+        }
+    }
+}
+
+// Returns the given fraction of a stake, rounded down.
+fn fraction_of(stake: &BigInt, fraction: f64) -> BigInt {
+    let precision = 1_000_000_000u64;
+    stake * BigInt::from((fraction * precision as f64).round() as u64) / BigInt::from(precision)
+}
+
+// Returns a uniformly random node among those with non-zero stake, or None if there is none.
+fn random_active_node(stakes: &[BigInt], rng: &mut ThreadRng) -> Option<usize> {
+    let active: Vec<usize> = (0..stakes.len()).filter(|&n| stakes[n].is_positive()).collect();
+    (!active.is_empty()).then(|| active[rng.random_range(0..active.len())])
+}
+
+// New nodes join the system. The number of joining nodes per epoch is Poisson-distributed with
+// the given mean. Each new node starts with the stake of a uniformly random existing node.
+pub struct NodeJoins {
+    pub joins_per_epoch: f64,
+}
+
+impl ChurnModel for NodeJoins {
+    fn evolve(&self, stakes: &mut Vec<BigInt>, rng: &mut ThreadRng) {
+        let joins = Poisson::new(self.joins_per_epoch).unwrap().sample(rng) as usize;
+        for _ in 0..joins {
+            if let Some(node) = random_active_node(stakes, rng) {
+                stakes.push(stakes[node].clone());
+            }
+        }
+    }
+}
+
+// Each node leaves the system (i.e., its stake drops to zero) independently with the given
+// probability per epoch. The last remaining node never leaves.
+pub struct NodeExits {
+    pub exit_probability: f64,
+}
+
+impl ChurnModel for NodeExits {
+    fn evolve(&self, stakes: &mut Vec<BigInt>, rng: &mut ThreadRng) {
+        let mut active = stakes.iter().filter(|s| s.is_positive()).count();
+        for stake in stakes.iter_mut() {
+            if active > 1 && stake.is_positive() && rng.random_bool(self.exit_probability) {
+                *stake = BigInt::zero();
+                active -= 1;
+            }
+        }
+    }
+}
+
+// With the given probability per epoch, each node loses a fraction of its stake to delegators
+// moving their stake to a uniformly random other node.
+pub struct DelegationMoves {
+    pub move_probability: f64,
+    pub moved_fraction: f64,
+}
+
+impl ChurnModel for DelegationMoves {
+    fn evolve(&self, stakes: &mut Vec<BigInt>, rng: &mut ThreadRng) {
+        for node in 0..stakes.len() {
+            if !stakes[node].is_positive() || !rng.random_bool(self.move_probability) {
+                continue;
+            }
+            if let Some(target) = random_active_node(stakes, rng).filter(|&t| t != node) {
+                let moved = fraction_of(&stakes[node], self.moved_fraction);
+                stakes[node] -= &moved;
+                stakes[target] += moved;
+            }
+        }
+    }
+}
+
+// Every node earns rewards proportional to its stake, which are added to its stake.
+pub struct RewardCompounding {
+    pub reward_rate: f64,
+}
+
+impl ChurnModel for RewardCompounding {
+    fn evolve(&self, stakes: &mut Vec<BigInt>, _rng: &mut ThreadRng) {
+        for stake in stakes.iter_mut() {
+            *stake += fraction_of(stake, self.reward_rate);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stakes(values: &[u64]) -> Vec<BigInt> {
+        values.iter().map(|&v| BigInt::from(v)).collect()
+    }
+
+    #[test]
+    fn churn_preserves_or_compounds_stake() {
+        let mut rng = rand::rng();
+        let mut values = stakes(&[1000, 2000, 3000, 4000]);
+        DelegationMoves { move_probability: 1.0, moved_fraction: 0.5 }.evolve(&mut values, &mut rng);
+        assert_eq!(values.iter().sum::<BigInt>(), BigInt::from(10000));
+
+        let mut values = stakes(&[1000, 2000, 3000, 4000]);
+        RewardCompounding { reward_rate: 0.1 }.evolve(&mut values, &mut rng);
+        assert_eq!(values, stakes(&[1100, 2200, 3300, 4400]));
+
+        NodeExits { exit_probability: 1.0 }.evolve(&mut values, &mut rng);
+        assert_eq!(values.iter().filter(|s| s.is_positive()).count(), 1);
+    }
+}
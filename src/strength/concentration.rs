@@ -0,0 +1,80 @@
+use crate::strength::stake_distribution::StakeDistribution;
+
+// Metrics of how concentrated the stake of a distribution is among few nodes.
+
+// Returns the stakes of all nodes sorted in ascending order.
+fn sorted_stakes<D: StakeDistribution + ?Sized>(distribution: &D) -> Vec<f64> {
+    let mut stakes: Vec<f64> = (0..distribution.num_nodes()).map(|n| distribution.stake_of(n)).collect();
+    stakes.sort_unstable_by(|a, b| a.total_cmp(b));
+    stakes
+}
+
+// Returns the Gini coefficient of the stake distribution, between 0 (all nodes have equal stake)
+// and 1 - 1/num_nodes (one node has all the stake).
+pub fn gini_coefficient<D: StakeDistribution>(distribution: &D) -> f64 {
+    let stakes = sorted_stakes(distribution);
+    let n = stakes.len() as f64;
+    let weighted_sum: f64 = stakes.iter().enumerate().map(|(i, s)| (i as f64 + 1.0) * s).sum();
+    let total: f64 = stakes.iter().sum();
+
+    (2.0 * weighted_sum) / (n * total) - (n + 1.0) / n
+}
+
+// Returns the Herfindahl-Hirschman index (the sum of squared stake fractions), between
+// 1/num_nodes (all nodes have equal stake) and 1 (one node has all the stake).
+pub fn herfindahl_hirschman_index<D: StakeDistribution>(distribution: &D) -> f64 {
+    (0..distribution.num_nodes()).map(|n| distribution.stake_of(n).powi(2)).sum()
+}
+
+// Returns the Nakamoto coefficient, i.e., the smallest number of nodes that together hold
+// more than a fraction threshold of the total stake.
+pub fn nakamoto_coefficient<D: StakeDistribution>(distribution: &D, threshold: f64) -> usize {
+    let mut sum = 0.0;
+    for (i, stake) in sorted_stakes(distribution).iter().rev().enumerate() {
+        sum += stake;
+        if sum > threshold {
+            return i + 1;
+        }
+    }
+    distribution.num_nodes()
+}
+
+// Returns the Lorenz curve of the stake distribution as points (x, y), where y is the fraction of the
+// total stake held by the fraction x of the nodes with the least stake. Starts at (0, 0) and ends
+// at (1, 1), with one point per node.
+pub fn lorenz_curve<D: StakeDistribution + ?Sized>(distribution: &D) -> Vec<(f64, f64)> {
+    let stakes = sorted_stakes(distribution);
+    let n = stakes.len() as f64;
+    let total: f64 = stakes.iter().sum();
+    let mut sum = 0.0;
+    let mut curve = vec![(0.0, 0.0)];
+    for (i, stake) in stakes.iter().enumerate() {
+        sum += stake;
+        curve.push(((i + 1) as f64 / n, sum / total));
+    }
+    curve
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strength::stake_distribution::{ExponentialStakeDistribution, UniformStakeDistribution};
+    use crate::test_util::approx_equal;
+
+    #[test]
+    fn uniform_concentration() {
+        let distribution = UniformStakeDistribution::new(10);
+        assert!(approx_equal(gini_coefficient(&distribution), 0.0));
+        assert!(approx_equal(herfindahl_hirschman_index(&distribution), 0.1));
+        assert_eq!(nakamoto_coefficient(&distribution, 1.0/3.0), 4);
+        assert!(lorenz_curve(&distribution).iter().all(|&(x, y)| approx_equal(x, y)));
+    }
+
+    #[test]
+    fn exponential_concentration() {
+        let distribution = ExponentialStakeDistribution::new(4, 0.5);
+        assert!(approx_equal(gini_coefficient(&distribution), 0.3125));
+        assert!(approx_equal(herfindahl_hirschman_index(&distribution), 0.25 + 0.0625 + 2.0 * 0.015625));
+        assert_eq!(nakamoto_coefficient(&distribution, 0.5), 2);
+    }
+}
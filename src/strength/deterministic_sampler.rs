@@ -0,0 +1,75 @@
+use num_bigint::{BigInt, Sign};
+use sha2::{Digest, Sha256};
+use crate::strength::stake_distribution::CustomStakeDistribution;
+use crate::strength::validator_sample::ValidatorSample;
+
+// DeterministicSampler selects validator samples as a deterministic function of a randomness seed
+// and the (integer) stakes of the nodes, so that the selection can be reproduced exactly by any
+// implementation of the same algorithm (e.g., by the nodes themselves):
+//
+// For each seat, a counter (starting at 0 and never reset) is hashed together with the seed as
+//     h = SHA-256(seed || counter),
+// where counter is encoded as 8 bytes big-endian. h is interpreted as a 256-bit big-endian unsigned
+// integer. If h >= floor(2^256 / W) * W, where W is the total stake, h is discarded and the counter
+// is incremented until an h below this limit is found (so that the result is exactly uniform).
+// Then r = h mod W is a stake unit chosen uniformly at random and the seat goes to the node
+// owning that stake unit: the smallest k such that stake_0 + ... + stake_k > r.
+// The counter is incremented after every hash, whether discarded or not.
+pub struct DeterministicSampler<'a> {
+    distribution: &'a CustomStakeDistribution,
+    // Hashes at or above this limit are discarded.
+    limit: BigInt,
+}
+
+impl<'a> DeterministicSampler<'a> {
+    pub fn new(distribution: &'a CustomStakeDistribution) -> Self {
+        let total_stake = distribution.total_stake();
+        let limit = (BigInt::from(1) << 256) / total_stake * total_stake;
+        Self { distribution, limit }
+    }
+
+    // Returns the nodes selected for each seat, in the order of selection.
+    pub fn committee(&self, seed: &[u8], size: usize) -> Vec<usize> {
+        let mut counter: u64 = 0;
+        let mut nodes = Vec::with_capacity(size);
+        while nodes.len() < size {
+            let hash = Sha256::new()
+                .chain_update(seed)
+                .chain_update(counter.to_be_bytes())
+                .finalize();
+            counter += 1;
+
+            let value = BigInt::from_bytes_be(Sign::Plus, &hash);
+            if value < self.limit {
+                nodes.push(self.distribution.node_at(&(value % self.distribution.total_stake())));
+            }
+        }
+
+        nodes
+    }
+
+    // Returns the validator sample selected by committee().
+    pub fn sample(&self, seed: &[u8], size: usize) -> ValidatorSample<'a, CustomStakeDistribution> {
+        ValidatorSample::from_nodes(self.committee(seed, size), self.distribution)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test vectors for other implementations of the sampling algorithm.
+    #[test]
+    fn test_vectors() {
+        let distribution = CustomStakeDistribution::from_plain("1\n2\n3\n4\n");
+        let sampler = DeterministicSampler::new(&distribution);
+        assert_eq!(sampler.committee(&[0u8; 32], 10), vec![3, 0, 3, 0, 3, 2, 2, 1, 2, 3]);
+        assert_eq!(sampler.committee(b"validator-sampling", 10), vec![2, 1, 1, 1, 3, 0, 2, 2, 1, 1]);
+
+        let distribution = CustomStakeDistribution::from_plain(
+            "31118352477120265399754826446828\n0\n28919200887372928203572638289528\n22251773366036244399552996054771\n"
+        );
+        let sampler = DeterministicSampler::new(&distribution);
+        assert_eq!(sampler.committee(&[0xff; 32], 10), vec![0, 2, 0, 2, 0, 0, 3, 0, 0, 3]);
+    }
+}
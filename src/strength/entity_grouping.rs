@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use crate::strength::stake_distribution::{CustomStakeDistribution, StakeDistribution};
+
+// EntityGrouping maps nodes to the entities (e.g., operators) running them.
+// Nodes run by the same entity fail (or get corrupted) together.
+// Entity IDs are integers starting at 0. Every node belongs to exactly one entity and
+// nodes without an explicitly assigned entity form an entity of their own.
+pub struct EntityGrouping {
+    entity_of: Vec<usize>,
+    entity_names: Vec<String>,
+}
+
+impl EntityGrouping {
+    // Loads the grouping from a file containing one `node,entity` pair per line, where node is
+    // either the node ID or the node's address in the given distribution.
+    pub fn from_file(file_name: &str, distribution: &CustomStakeDistribution) -> Self {
+        let file_content = fs::read_to_string(file_name).unwrap();
+        let node_ids: HashMap<String, usize> = (0..distribution.num_nodes())
+            .filter_map(|n| distribution.node_info(n).map(|info| (info.address.clone(), n)))
+            .collect();
+
+        let mut entities = vec![None; distribution.num_nodes()];
+        for (i, line) in file_content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (node, entity) = line.split_once(',')
+                .unwrap_or_else(|| panic!("Invalid entity file line {}: expected node,entity", i + 1));
+            let node = node_ids.get(node.trim()).copied()
+                .or_else(|| node.trim().parse().ok())
+                .filter(|&n| n < distribution.num_nodes())
+                .unwrap_or_else(|| panic!("Invalid entity file line {}: unknown node '{}'", i + 1, node));
+            entities[node] = Some(entity.trim().to_owned());
+        }
+
+        Self::new(distribution, entities)
+    }
+
+    // Groups the nodes by the operator field of their identities (as loaded from CSV or JSON).
+    pub fn from_operators(distribution: &CustomStakeDistribution) -> Self {
+        let entities = (0..distribution.num_nodes())
+            .map(|n| distribution.node_info(n).and_then(|info| info.operator.clone()))
+            .collect();
+
+        Self::new(distribution, entities)
+    }
+
+    // Returns the grouping from the given file if it exists, otherwise the grouping by operators
+    // if the distribution contains operators. Returns None if neither is available.
+    pub fn load(file_name: &str, distribution: &CustomStakeDistribution) -> Option<Self> {
+        if Path::new(file_name).exists() {
+            Some(Self::from_file(file_name, distribution))
+        } else if (0..distribution.num_nodes()).any(|n| distribution.node_info(n).is_some_and(|info| info.operator.is_some())) {
+            Some(Self::from_operators(distribution))
+        } else {
+            None
+        }
+    }
+
+    // Returns the trivial grouping where each node is an entity of its own.
+    pub fn per_node<D: StakeDistribution>(distribution: &D) -> Self {
+        Self::new(distribution, vec![None; distribution.num_nodes()])
+    }
+
+    fn new<D: StakeDistribution>(distribution: &D, entities: Vec<Option<String>>) -> Self {
+        let mut entity_ids = HashMap::new();
+        let mut entity_names = vec![];
+        let mut entity_of = vec![];
+        for (node, entity) in entities.into_iter().enumerate() {
+            let name = entity.unwrap_or_else(|| distribution.node_name(node));
+            let id = *entity_ids.entry(name.clone()).or_insert_with(|| {
+                entity_names.push(name);
+                entity_names.len() - 1
+            });
+            entity_of.push(id);
+        }
+
+        Self { entity_of, entity_names }
+    }
+
+    pub fn entity_of(&self, node: usize) -> usize {
+        self.entity_of[node]
+    }
+
+    pub fn num_entities(&self) -> usize {
+        self.entity_names.len()
+    }
+
+    pub fn entity_name(&self, entity: usize) -> &str {
+        &self.entity_names[entity]
+    }
+
+    // Returns the combined stake of all nodes run by each entity, indexed by entity ID.
+    pub fn entity_stakes<D: StakeDistribution>(&self, distribution: &D) -> Vec<f64> {
+        let mut stakes = vec![0.0; self.num_entities()];
+        for (node, &entity) in self.entity_of.iter().enumerate() {
+            stakes[entity] += distribution.stake_of(node);
+        }
+        stakes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grouping_by_operators() {
+        let distribution = CustomStakeDistribution::from_csv("a,1,op-1\nb,2\nc,3,op-1\nd,4,op-2\n");
+        let grouping = EntityGrouping::from_operators(&distribution);
+        assert_eq!(grouping.num_entities(), 3);
+        assert_eq!(grouping.entity_of(0), grouping.entity_of(2));
+        assert_ne!(grouping.entity_of(0), grouping.entity_of(1));
+        assert_eq!(grouping.entity_name(grouping.entity_of(1)), "b");
+        assert_eq!(grouping.entity_name(grouping.entity_of(3)), "op-2");
+        assert_eq!(grouping.entity_stakes(&distribution)[grouping.entity_of(0)], 0.4);
+    }
+}
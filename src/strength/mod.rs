@@ -1,16 +1,35 @@
-mod stake_distribution;
-mod validator_sample;
-mod simulation;
+pub mod stake_distribution;
+pub mod validator_sample;
+pub mod simulation;
+pub mod entity_grouping;
+pub mod concentration;
+pub mod churn;
+pub mod adversary;
+pub mod bribery;
+pub mod deterministic_sampler;
+pub mod sortition;
+pub mod capping;
 
 use stake_distribution::*;
 use simulation::Simulation;
+use entity_grouping::EntityGrouping;
+use adversary::*;
+use bribery::*;
+use deterministic_sampler::DeterministicSampler;
+use validator_sample::{Selection, VoteWeight};
+use capping::CappedStakeDistribution;
+use crate::table::Table;
+use std::path::Path;
 
 pub fn simulate() {
     let num_nodes = 296;
     let sample_size = 111;
     let exp_distribution_p = 0.1;
     let actual_distribution_file = "node-stakes-september-2025";
+    let entity_file = "node-entities-september-2025";
+    let price_file = "node-prices-september-2025";
     let iterations = 1000;
+    let largest_validators = 5;
 
     println!();
     println!("================================================================================");
@@ -56,4 +75,154 @@ pub fn simulate() {
     println!("Weak quorum stake: avg {} (std-dev {})", weak_quorum_avg, weak_quorum_std_dev);
     println!("Strong quorum stake: avg {} (std-dev {})", strong_quorum_avg, strong_quorum_std_dev);
     println!("Total stake: avg {} (std-dev {})", total_avg, total_std_dev);
-}
\ No newline at end of file
+    println!("Largest validators:");
+    let mut nodes: Vec<usize> = (0..actual_distribution.num_nodes()).collect();
+    nodes.sort_unstable_by(|&a, &b| actual_distribution.stake_of(b).total_cmp(&actual_distribution.stake_of(a)));
+    for node in nodes.into_iter().take(largest_validators) {
+        println!("  {}: {}", actual_distribution.node_name(node), actual_distribution.stake_of(node));
+    }
+    println!();
+
+    match EntityGrouping::load(entity_file, &actual_distribution) {
+        Some(grouping) => {
+            let mut simulation = Simulation::new(&actual_distribution).with_grouping(&grouping);
+            simulation.run(sample_size, iterations);
+            let (strong_quorum_avg, strong_quorum_std_dev) = simulation.entity_strong_quorum_stake();
+            let (weak_quorum_avg, weak_quorum_std_dev) = simulation.entity_weak_quorum_stake();
+            println!("Actual current distribution, grouped into {} entities:", grouping.num_entities());
+            println!("Weak quorum stake: avg {} (std-dev {})", weak_quorum_avg, weak_quorum_std_dev);
+            println!("Strong quorum stake: avg {} (std-dev {})", strong_quorum_avg, strong_quorum_std_dev);
+            println!("Largest entities:");
+            let entity_stakes = grouping.entity_stakes(&actual_distribution);
+            let mut entities: Vec<usize> = (0..grouping.num_entities()).collect();
+            entities.sort_unstable_by(|&a, &b| entity_stakes[b].total_cmp(&entity_stakes[a]));
+            for entity in entities.into_iter().take(largest_validators) {
+                println!("  {}: {}", grouping.entity_name(entity), entity_stakes[entity]);
+            }
+        }
+        None => {
+            println!("No entity grouping for the actual distribution (no file '{}' and no operators).", entity_file);
+        }
+    }
+    println!();
+
+    println!("Adaptive adversary on the actual distribution:");
+    println!("Probability that an adversary corrupting the lowest-stake nodes of a revealed sample, at a rate of");
+    println!("one node per time unit, reaches a weak / strong quorum before the sample becomes active.");
+    let mut table = Table::new(&["budget", "delay", "weak quorum", "strong quorum"]);
+    let budgets = [
+        CorruptionBudget::Stake(0.1),
+        CorruptionBudget::Stake(0.2),
+        CorruptionBudget::Stake(0.4),
+        CorruptionBudget::Nodes(20),
+        CorruptionBudget::Nodes(40),
+    ];
+    for budget in budgets {
+        for delay in [10.0, 50.0, 100.0] {
+            let adversary = AdaptiveAdversary { budget, corruption_rate: 1.0, delay };
+            let (weak, strong) = adversary.simulate(&actual_distribution, sample_size, iterations);
+            let budget = match budget {
+                CorruptionBudget::Stake(stake) => format!("stake {}", stake),
+                CorruptionBudget::Nodes(nodes) => format!("{} nodes", nodes),
+            };
+            table.add_row(vec![budget, delay.to_string(), format!("{:.3}", weak), format!("{:.3}", strong)]);
+        }
+    }
+    print!("{}", table.to_terminal());
+    println!();
+
+    println!("Minimal bribes for quorums of samples from the actual distribution (in units of total stake):");
+    let mut price_functions: Vec<(String, Box<dyn PriceFunction>)> = vec![
+        ("proportional to stake".to_owned(), Box::new(ProportionalPrice { price_per_stake: 1.0 })),
+        ("fixed 0.001 + 10% of stake slashable".to_owned(), Box::new(FixedPlusSlashablePrice { fixed: 0.001, slashable_fraction: 0.1 })),
+    ];
+    if Path::new(price_file).exists() {
+        price_functions.push((format!("from file '{}'", price_file), Box::new(TablePrice::from_file(price_file))));
+    } else {
+        println!("(No per-node prices: file '{}' does not exist.)", price_file);
+    }
+    for (name, prices) in &price_functions {
+        let mut simulation = Simulation::new(&actual_distribution).with_prices(prices.as_ref());
+        simulation.run(sample_size, iterations);
+        let (strong_bribe_avg, strong_bribe_std_dev) = simulation.strong_quorum_bribe();
+        let (weak_bribe_avg, weak_bribe_std_dev) = simulation.weak_quorum_bribe();
+        println!("Price {}:", name);
+        println!("Weak quorum bribe: avg {} (std-dev {})", weak_bribe_avg, weak_bribe_std_dev);
+        println!("Strong quorum bribe: avg {} (std-dev {})", strong_bribe_avg, strong_bribe_std_dev);
+    }
+    println!();
+
+    println!("Biased randomness: quorum stakes of the actual distribution if the adversary chooses the");
+    println!("sample with the smallest strong quorum stake out of k independently drawn samples:");
+    for k in [1, 2, 4, 16] {
+        let mut simulation = Simulation::new(&actual_distribution).with_grinding(k);
+        simulation.run(sample_size, iterations);
+        let (strong_quorum_avg, strong_quorum_std_dev) = simulation.strong_quorum_stake();
+        let (weak_quorum_avg, weak_quorum_std_dev) = simulation.weak_quorum_stake();
+        println!("k = {}:", k);
+        println!("Weak quorum stake: avg {} (std-dev {})", weak_quorum_avg, weak_quorum_std_dev);
+        println!("Strong quorum stake: avg {} (std-dev {})", strong_quorum_avg, strong_quorum_std_dev);
+    }
+    println!();
+
+    println!("Quorum stakes of the actual distribution for different vote weights within the sample:");
+    for vote_weight in [VoteWeight::Seat, VoteWeight::Node, VoteWeight::Stake] {
+        let mut simulation = Simulation::new(&actual_distribution).with_vote_weight(vote_weight);
+        simulation.run(sample_size, iterations);
+        let (strong_quorum_avg, strong_quorum_std_dev) = simulation.strong_quorum_stake();
+        let (weak_quorum_avg, weak_quorum_std_dev) = simulation.weak_quorum_stake();
+        println!("One vote {}:", vote_weight.name());
+        println!("Weak quorum stake: avg {} (std-dev {})", weak_quorum_avg, weak_quorum_std_dev);
+        println!("Strong quorum stake: avg {} (std-dev {})", strong_quorum_avg, strong_quorum_std_dev);
+    }
+    println!();
+
+    println!("Quorum stakes of the actual distribution with different selection policies.");
+    println!("Stake cap x: each node is sampled with an effective stake of at most x (the excess is");
+    println!("redistributed to the other nodes). Seat cap m: no node holds more than m seats.");
+    println!("Top k + random: the k nodes with the most stake get one seat each, the other seats are sampled.");
+    let mut table = Table::new(&["policy", "weak quorum", "strong quorum", "total stake"]);
+    for selection in [
+        Selection::Random,
+        Selection::SeatCap(1),
+        Selection::SeatCap(2),
+        Selection::SeatCap(3),
+        Selection::TopKPlusRandom(5),
+        Selection::TopKPlusRandom(10),
+        Selection::TopKPlusRandom(20),
+    ] {
+        table.add_row(policy_row(&selection.name(), &actual_distribution, selection, sample_size, iterations));
+    }
+    for cap in [0.01, 0.02] {
+        let capped_distribution = CappedStakeDistribution::new(&actual_distribution, cap);
+        table.add_row(policy_row(&format!("stake cap {}", cap), &capped_distribution, Selection::Random, sample_size, iterations));
+    }
+    print!("{}", table.to_terminal());
+    for cap in [0.01, 0.02] {
+        let capped_distribution = CappedStakeDistribution::new(&actual_distribution, cap);
+        let capped_nodes = (0..actual_distribution.num_nodes())
+            .filter(|&n| capped_distribution.effective_stake_of(n) < actual_distribution.stake_of(n))
+            .count();
+        println!("Nodes affected by stake cap {}: {}", cap, capped_nodes);
+    }
+    println!();
+
+    let seed = [0u8; 32];
+    let sample = DeterministicSampler::new(&actual_distribution).sample(&seed, sample_size);
+    println!("Deterministic sample of the actual distribution for the all-zero 32-byte seed:");
+    println!("Distinct nodes: {}", sample.seats_by_node().len());
+    println!("Weak quorum stake: {}", sample.weak_quorum_stake());
+    println!("Strong quorum stake: {}", sample.strong_quorum_stake());
+    println!("Total stake: {}", sample.total_stake());
+}
+
+fn policy_row<D: StakeDistribution>(name: &str, distribution: &D, selection: Selection, sample_size: usize, iterations: usize) -> Vec<String> {
+    let mut simulation = Simulation::new(distribution).with_selection(selection);
+    simulation.run(sample_size, iterations);
+    vec![
+        name.to_owned(),
+        format!("{:.4}", simulation.weak_quorum_stake().0),
+        format!("{:.4}", simulation.strong_quorum_stake().0),
+        format!("{:.4}", simulation.total_stakes().0),
+    ]
+}
@@ -1,6 +1,8 @@
 use statrs::statistics::Statistics;
 use crate::strength::stake_distribution::StakeDistribution;
-use crate::strength::validator_sample::ValidatorSample;
+use crate::strength::validator_sample::{Selection, ValidatorSample, VoteWeight};
+use crate::strength::entity_grouping::EntityGrouping;
+use crate::strength::bribery::PriceFunction;
 
 // Simulation represents multiple iterations of validator sampling.
 // It is only concerned with the strength of the sample
@@ -9,10 +11,19 @@ use crate::strength::validator_sample::ValidatorSample;
 // are handled by the representativeness crate.
 pub struct Simulation<'a, D: StakeDistribution> {
     distribution: &'a D,
+    grouping: Option<&'a EntityGrouping>,
+    prices: Option<&'a dyn PriceFunction>,
+    draws: usize,
+    vote_weight: VoteWeight,
+    selection: Selection,
 
     weak_quorum_stakes: Vec<f64>,
     strong_quorum_stakes: Vec<f64>,
     total_stakes: Vec<f64>,
+    entity_weak_quorum_stakes: Vec<f64>,
+    entity_strong_quorum_stakes: Vec<f64>,
+    weak_quorum_bribes: Vec<f64>,
+    strong_quorum_bribes: Vec<f64>,
 }
 
 impl<'a, D: StakeDistribution> Simulation<'a, D> {
@@ -21,16 +32,83 @@ impl<'a, D: StakeDistribution> Simulation<'a, D> {
             total_stakes: vec![],
             strong_quorum_stakes: vec![],
             weak_quorum_stakes: vec![],
+            entity_weak_quorum_stakes: vec![],
+            entity_strong_quorum_stakes: vec![],
+            weak_quorum_bribes: vec![],
+            strong_quorum_bribes: vec![],
+            grouping: None,
+            prices: None,
+            draws: 1,
+            vote_weight: VoteWeight::Seat,
+            selection: Selection::Random,
             distribution
         }
     }
 
+    // Makes the simulation additionally compute the quorum stakes per entity of the given grouping.
+    pub fn with_grouping(mut self, grouping: &'a EntityGrouping) -> Self {
+        self.grouping = Some(grouping);
+        self
+    }
+
+    // Makes the simulation additionally compute the minimal bribes for quorums under the given prices.
+    // Bribes are not supported for votes weighted by stake (see with_vote_weight()).
+    pub fn with_prices(mut self, prices: &'a dyn PriceFunction) -> Self {
+        self.prices = Some(prices);
+        self.check_bribes();
+        self
+    }
+
+    // Models an adversary that can bias the randomness of the sampling (e.g., by grinding a
+    // randomness beacon) and choose the best out of the given number of independently drawn samples.
+    // In each iteration, the simulation then draws that many samples, picks the one with the
+    // smallest strong quorum stake and records all metrics of that sample.
+    pub fn with_grinding(mut self, draws: usize) -> Self {
+        self.draws = draws;
+        self
+    }
+
+    // Sets how the validators of each sample vote (see ValidatorSample::with_vote_weight()).
+    pub fn with_vote_weight(mut self, vote_weight: VoteWeight) -> Self {
+        self.vote_weight = vote_weight;
+        self.check_bribes();
+        self
+    }
+
+    // Panics if bribes are to be computed for votes weighted by stake, which node_prices() rejects.
+    fn check_bribes(&self) {
+        if self.prices.is_some() && self.vote_weight == VoteWeight::Stake {
+            panic!("Bribes are not supported for votes weighted by stake");
+        }
+    }
+
+    // Sets how the seats of each sample are assigned to nodes (see ValidatorSample::select()).
+    pub fn with_selection(mut self, selection: Selection) -> Self {
+        self.selection = selection;
+        self
+    }
+
     pub fn run(&mut self, sample_size: usize, iterations: usize) {
         for _ in 0..iterations {
-            let sample = ValidatorSample::new(sample_size, self.distribution);
+            let sample = (0..self.draws)
+                .map(|_| {
+                    let sample = ValidatorSample::select(sample_size, self.distribution, self.selection).with_vote_weight(self.vote_weight);
+                    (sample.strong_quorum_stake(), sample)
+                })
+                .min_by(|(a, _), (b, _)| a.total_cmp(b))
+                .map(|(_, sample)| sample)
+                .unwrap();
             self.weak_quorum_stakes.push(sample.weak_quorum_stake());
             self.strong_quorum_stakes.push(sample.strong_quorum_stake());
             self.total_stakes.push(sample.total_stake());
+            if let Some(grouping) = self.grouping {
+                self.entity_weak_quorum_stakes.push(sample.entity_weak_quorum_stake(grouping));
+                self.entity_strong_quorum_stakes.push(sample.entity_strong_quorum_stake(grouping));
+            }
+            if let Some(prices) = self.prices {
+                self.weak_quorum_bribes.push(sample.weak_quorum_bribe(prices));
+                self.strong_quorum_bribes.push(sample.strong_quorum_bribe(prices));
+            }
         }
     }
 
@@ -46,6 +124,16 @@ impl<'a, D: StakeDistribution> Simulation<'a, D> {
         )
     }
 
+    // Returns the strong quorum stakes of all iterations (e.g., for plotting their distribution).
+    pub fn strong_quorum_stakes(&self) -> &[f64] {
+        &self.strong_quorum_stakes
+    }
+
+    // Returns the weak quorum stakes of all iterations.
+    pub fn weak_quorum_stakes(&self) -> &[f64] {
+        &self.weak_quorum_stakes
+    }
+
     // Same as strong_quorum_stake(), except the quorum size is 1/3 instead of 2/3.
     pub fn weak_quorum_stake(&self) -> (f64, f64) {
         (
@@ -62,4 +150,54 @@ impl<'a, D: StakeDistribution> Simulation<'a, D> {
             self.total_stakes.iter().std_dev(),
         )
     }
-}
\ No newline at end of file
+
+    // Same as strong_quorum_stake(), but with nodes grouped into entities
+    // (see ValidatorSample::entity_strong_quorum_stake()). Requires a grouping to be set.
+    pub fn entity_strong_quorum_stake(&self) -> (f64, f64) {
+        (
+            self.entity_strong_quorum_stakes.iter().mean(),
+            self.entity_strong_quorum_stakes.iter().std_dev(),
+        )
+    }
+
+    // Same as weak_quorum_stake(), but with nodes grouped into entities. Requires a grouping to be set.
+    pub fn entity_weak_quorum_stake(&self) -> (f64, f64) {
+        (
+            self.entity_weak_quorum_stakes.iter().mean(),
+            self.entity_weak_quorum_stakes.iter().std_dev(),
+        )
+    }
+
+    // Returns the average and standard deviation of the minimal total price of bribing validators
+    // holding a strong quorum of seats (see ValidatorSample::strong_quorum_bribe()).
+    // Requires prices to be set.
+    pub fn strong_quorum_bribe(&self) -> (f64, f64) {
+        (
+            self.strong_quorum_bribes.iter().mean(),
+            self.strong_quorum_bribes.iter().std_dev(),
+        )
+    }
+
+    // Same as strong_quorum_bribe(), but for a weak quorum. Requires prices to be set.
+    pub fn weak_quorum_bribe(&self) -> (f64, f64) {
+        (
+            self.weak_quorum_bribes.iter().mean(),
+            self.weak_quorum_bribes.iter().std_dev(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strength::bribery::ProportionalPrice;
+    use crate::strength::stake_distribution::UniformStakeDistribution;
+
+    #[test]
+    #[should_panic(expected = "Bribes are not supported")]
+    fn bribes_with_stake_votes_are_rejected() {
+        let distribution = UniformStakeDistribution::new(10);
+        let prices = ProportionalPrice { price_per_stake: 1.0 };
+        let _ = Simulation::new(&distribution).with_vote_weight(VoteWeight::Stake).with_prices(&prices);
+    }
+}
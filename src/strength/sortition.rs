@@ -0,0 +1,34 @@
+use rand::distr::Distribution;
+use rand_distr::Binomial;
+use crate::strength::stake_distribution::StakeDistribution;
+use crate::strength::validator_sample::ValidatorSample;
+
+// SortitionSampler selects validator samples using Algorand-style cryptographic sortition.
+// The total stake is divided into stake_units units and each unit is independently selected with
+// probability expected_size / stake_units. A node gets one seat per selected unit of its stake,
+// i.e., a number of seats following Bin(units of the node, expected_size / stake_units).
+// Unlike with ValidatorSample::new(), the size of the sample is random, with mean expected_size.
+pub struct SortitionSampler<'a, D: StakeDistribution> {
+    distribution: &'a D,
+    expected_size: f64,
+    stake_units: u64,
+}
+
+impl<'a, D: StakeDistribution> SortitionSampler<'a, D> {
+    pub fn new(distribution: &'a D, expected_size: f64, stake_units: u64) -> Self {
+        Self { distribution, expected_size, stake_units }
+    }
+
+    pub fn sample(&self) -> ValidatorSample<'a, D> {
+        let mut rng = rand::rng();
+        let unit_probability = self.expected_size / self.stake_units as f64;
+        let mut nodes = vec![];
+        for node in 0..self.distribution.num_nodes() {
+            let units = self.distribution.stake_units(node, self.stake_units);
+            let seats = Binomial::new(units, unit_probability).unwrap().sample(&mut rng);
+            nodes.extend(std::iter::repeat_n(node, seats as usize));
+        }
+
+        ValidatorSample::from_nodes(nodes, self.distribution)
+    }
+}
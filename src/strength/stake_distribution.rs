@@ -1,9 +1,11 @@
-use rand::Rng;
+use rand::{Rng, RngCore};
 use std::{fs};
+use std::path::Path;
 use std::str::FromStr;
-use num_bigint::BigInt;
+use num_bigint::{BigInt, Sign};
 use num_rational::BigRational;
-use rand_distr::num_traits::ToPrimitive;
+use rand_distr::num_traits::{Signed, ToPrimitive, Zero};
+use serde_json::Value;
 
 // StakeDistribution represents the assignment of stake to nodes.
 // The stake value is a number between 0 and 1.
@@ -15,6 +17,17 @@ pub trait StakeDistribution {
     fn stake_of(&self, node: usize) -> f64;
     fn num_nodes(&self) -> usize;
 
+    // Returns a human-readable name of a node to be used in reports.
+    fn node_name(&self, node: usize) -> String {
+        format!("node {}", node)
+    }
+
+    // Returns the number of units of stake held by a given node, if the total stake is divided into
+    // total_units units, rounded to the nearest integer.
+    fn stake_units(&self, node: usize, total_units: u64) -> u64 {
+        (self.stake_of(node) * total_units as f64).round() as u64
+    }
+
     // Returns a random node id, where the probability of picking a particular node is
     // proportional to the node's stake.
     fn sample(&self) -> usize {
@@ -92,59 +105,270 @@ impl StakeDistribution for ExponentialStakeDistribution {
     }
 }
 
+// Identity and metadata of a node, as found in stake snapshots that name their validators
+// (e.g., CSV or JSON exports of a validator set). Operator and pool are optional.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NodeInfo {
+    pub address: String,
+    pub operator: Option<String>,
+    pub pool: Option<String>,
+}
+
 pub struct CustomStakeDistribution {
-    stakes: Vec<f64>
+    stakes: Vec<BigInt>,
+    total_stake: BigInt,
+    // cumulative_stakes[k] is the sum of the stakes of nodes 0 to k (inclusive).
+    cumulative_stakes: Vec<BigInt>,
+    // Stakes as fractions of the total stake, only used for reporting.
+    fractions: Vec<f64>,
+    identities: Vec<NodeInfo>,
 }
 
 // The CustomStakeDistribution implements a lookup table with an arbitrary distribution of the
-// stake. It can be loaded from a file containing one integer per line, where the k-th line
-// represents the amount of stake of the k-th node (numbering starting from zero).
-// Upon initialization, all stakes are normalized to be expressed by floating point numbers that
-// sum up to one.
+// stake. It can be loaded from a file in one of the following formats:
+// - Plain: one integer per line, where the k-th line represents the amount of stake of the k-th
+//   node (numbering starting from zero). Nodes have no identities.
+// - CSV (*.csv): one node per line as `address,stake[,operator,pool]` (fields may be quoted), with
+//   an optional header.
+// - JSON (*.json): an array of objects with the fields "address", "stake" (integer or string),
+//   and optionally "operator" and "pool". The array may also be wrapped in an object under the
+//   key "validators".
+// The stakes are kept as exact integers and sampling is performed over the integer weights, so
+// no precision is lost and even the tiniest stakes can be sampled. Only stake_of() expresses stakes
+// as floating point fractions of the total stake.
+// For the CSV and JSON formats, the k-th node keeps the identity of the k-th entry.
 impl CustomStakeDistribution {
     pub fn from_file(file_name: &str) -> Self {
-        // Read all lines of the input file.
         let file_content = fs::read_to_string(file_name).unwrap();
-        let lines : Vec<String> = file_content.lines().map(|l| l.to_owned()).collect();
+        match Path::new(file_name).extension().and_then(|ext| ext.to_str()) {
+            Some("csv") => Self::from_csv(&file_content),
+            Some("json") => Self::from_json(&file_content),
+            _ => Self::from_plain(&file_content),
+        }
+    }
+
+    // Parses the plain format, one integer per line.
+    pub fn from_plain(content: &str) -> Self {
+        let stakes_big = content.lines()
+            .map(|line| parse_stake(line.trim()))
+            .collect();
+
+        Self::new(stakes_big, vec![])
+    }
 
-        // Convert string representations of stake to BigInt numbers and calculate total stake.
+    // Parses the CSV format `address,stake[,operator,pool]`. Empty lines are ignored.
+    // The first line is treated as a header if its stake field is not a number. Fields may be
+    // quoted (with quotes inside doubled, as in RFC 4180), but must not span multiple lines.
+    pub fn from_csv(content: &str) -> Self {
         let mut stakes_big = vec![];
-        for line in lines {
-            stakes_big.push(BigInt::from_str(line.as_str()).unwrap());
+        let mut identities = vec![];
+        for (i, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let fields = split_csv_line(line, i + 1);
+            if fields.len() < 2 || fields.len() > 4 {
+                panic!("Invalid stake CSV line {}: expected address,stake[,operator,pool]", i + 1);
+            }
+            if i == 0 && BigInt::from_str(&fields[1]).is_err() {
+                continue; // Skip header.
+            }
+            let optional_field = |k: usize| fields.get(k).filter(|f| !f.is_empty()).cloned();
+            stakes_big.push(parse_stake(&fields[1]));
+            identities.push(NodeInfo {
+                address: fields[0].clone(),
+                operator: optional_field(2),
+                pool: optional_field(3),
+            });
+        }
+
+        Self::new(stakes_big, identities)
+    }
+
+    // Parses a JSON validator set export.
+    pub fn from_json(content: &str) -> Self {
+        let json: Value = serde_json::from_str(content).unwrap();
+        let validators = match &json {
+            Value::Array(validators) => validators,
+            Value::Object(object) => match object.get("validators") {
+                Some(Value::Array(validators)) => validators,
+                _ => panic!("Invalid stake JSON: expected a \"validators\" array"),
+            },
+            _ => panic!("Invalid stake JSON: expected an array of validators"),
+        };
+
+        let mut stakes_big = vec![];
+        let mut identities = vec![];
+        for validator in validators {
+            let string_field = |key: &str| validator.get(key).and_then(|v| v.as_str()).map(|v| v.to_owned());
+            let stake = match validator.get("stake") {
+                Some(Value::String(stake)) => parse_stake(stake),
+                Some(Value::Number(stake)) => match stake.as_u64() {
+                    Some(stake) => BigInt::from(stake),
+                    None => panic!("Invalid stake JSON: stake {} is not a 64-bit integer (use a string)", stake),
+                },
+                _ => panic!("Invalid stake JSON: validator without stake: {}", validator),
+            };
+            stakes_big.push(stake);
+            identities.push(NodeInfo {
+                address: string_field("address")
+                    .unwrap_or_else(|| panic!("Invalid stake JSON: validator without address: {}", validator)),
+                operator: string_field("operator"),
+                pool: string_field("pool"),
+            });
+        }
+
+        Self::new(stakes_big, identities)
+    }
+
+    // Creates a distribution directly from integer stakes, with nodes that have no identities.
+    pub fn from_stakes(stakes: Vec<BigInt>) -> Self {
+        Self::new(stakes, vec![])
+    }
+
+    fn new(stakes: Vec<BigInt>, identities: Vec<NodeInfo>) -> Self {
+        let cumulative_stakes: Vec<BigInt> = stakes.iter()
+            .scan(BigInt::zero(), |sum, stake| {
+                *sum += stake;
+                Some(sum.clone())
+            })
+            .collect();
+        let total_stake = cumulative_stakes.last().cloned().unwrap_or_default();
+        if !total_stake.is_positive() {
+            panic!("Invalid stake distribution: total stake must be positive.");
         }
-        let total_stake : BigInt = stakes_big.iter().sum();
 
         // Express each node's stake as a fraction of the total stake.
-        let mut stakes = vec![];
-        for stake in stakes_big {
-            stakes.push(BigRational::new(stake, total_stake.clone()).to_f64().unwrap());
+        let fractions = stakes.iter()
+            .map(|stake| BigRational::new(stake.clone(), total_stake.clone()).to_f64().unwrap())
+            .collect();
+
+        Self{stakes, total_stake, cumulative_stakes, fractions, identities}
+    }
+
+    // Returns the (integer) stakes of all nodes, as loaded from the input.
+    pub fn stakes(&self) -> &[BigInt] {
+        &self.stakes
+    }
+
+    pub fn total_stake(&self) -> &BigInt {
+        &self.total_stake
+    }
+
+    // Returns the node that owns the given unit of stake, where 0 <= stake_unit < total_stake and
+    // stake units are assigned to nodes in order (node 0 owns the first stake_0 units etc.).
+    pub fn node_at(&self, stake_unit: &BigInt) -> usize {
+        self.cumulative_stakes.partition_point(|sum| sum <= stake_unit)
+    }
+
+    // Returns the identity of a node, if the distribution was loaded from a format that has them.
+    pub fn node_info(&self, node: usize) -> Option<&NodeInfo> {
+        self.identities.get(node)
+    }
+}
+
+// Splits a line of CSV into its fields. Unquoted fields are trimmed, quoted fields are kept as they
+// are (without the quotes). line_number is only used for error messages.
+fn split_csv_line(line: &str, line_number: usize) -> Vec<String> {
+    let mut fields = vec![];
+    let mut chars = line.chars().peekable();
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        let mut field = String::new();
+        if chars.next_if_eq(&'"').is_some() {
+            loop {
+                match chars.next() {
+                    Some('"') if chars.next_if_eq(&'"').is_some() => field.push('"'),
+                    Some('"') => break,
+                    Some(c) => field.push(c),
+                    None => panic!("Invalid stake CSV line {}: unterminated quoted field", line_number),
+                }
+            }
+            while chars.next_if(|c| c.is_whitespace()).is_some() {}
+            if chars.peek().is_some_and(|&c| c != ',') {
+                panic!("Invalid stake CSV line {}: unexpected characters after a quoted field", line_number);
+            }
+        } else {
+            while let Some(c) = chars.next_if(|&c| c != ',') {
+                if c == '"' {
+                    panic!("Invalid stake CSV line {}: quote inside an unquoted field", line_number);
+                }
+                field.push(c);
+            }
+            field = field.trim().to_owned();
+        }
+        fields.push(field);
+        if chars.next().is_none() {
+            return fields;
         }
+    }
+}
+
+fn parse_stake(stake: &str) -> BigInt {
+    match BigInt::from_str(stake) {
+        Ok(stake) if !stake.is_negative() => stake,
+        _ => panic!("Invalid stake value: '{}'", stake),
+    }
+}
 
-        Self{stakes}
+// Returns a uniformly random integer r such that 0 <= r < bound, using rejection sampling.
+fn random_below(bound: &BigInt) -> BigInt {
+    let bits = bound.bits();
+    let mut bytes = vec![0u8; bits.div_ceil(8) as usize];
+    loop {
+        rand::rng().fill_bytes(&mut bytes);
+        // Clear the excess high bits, so that each attempt succeeds with probability at least 1/2.
+        if !bits.is_multiple_of(8) {
+            bytes[0] &= (1u8 << (bits % 8)) - 1;
+        }
+        let candidate = BigInt::from_bytes_be(Sign::Plus, &bytes);
+        if &candidate < bound {
+            return candidate;
+        }
     }
 }
 
 impl StakeDistribution for CustomStakeDistribution {
     fn stake_of(&self, node: usize) -> f64 {
-        if node >= self.stakes.len() {
+        if node >= self.fractions.len() {
             0.0
         } else {
-            self.stakes[node]
+            self.fractions[node]
         }
     }
 
     fn num_nodes(&self) -> usize {
         self.stakes.len()
     }
+
+    // Computed from the exact integer stakes.
+    fn stake_units(&self, node: usize, total_units: u64) -> u64 {
+        match self.stakes.get(node) {
+            Some(stake) => {
+                let units: BigInt = (stake * total_units * 2u32 + &self.total_stake) / (&self.total_stake * 2u32);
+                units.to_u64().unwrap()
+            }
+            None => 0,
+        }
+    }
+
+    // Samples over the exact integer stakes.
+    fn sample(&self) -> usize {
+        self.node_at(&random_below(&self.total_stake))
+    }
+
+    fn node_name(&self, node: usize) -> String {
+        match self.node_info(node) {
+            Some(info) => info.address.clone(),
+            None => format!("node {}", node),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    fn approx_equal(a: f64, b: f64) -> bool {
-        (a - b).abs() < 1e-10
-    }
+    use crate::test_util::approx_equal;
 
     #[test]
     fn exponential_stake_distribution_0_5() {
@@ -165,4 +389,69 @@ mod tests {
         assert!(approx_equal(distribution.stake_of(2), 0.2*0.2*0.8));
         assert!(approx_equal(distribution.stake_of(3), 0.2*0.2*0.2));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn custom_stake_distribution_csv() {
+        let distribution = CustomStakeDistribution::from_csv(
+            "address,stake,operator,pool\nval-a,3,op-1,\nval-b,1\n\nval-c,4,op-1,pool-x\n"
+        );
+        assert_eq!(distribution.num_nodes(), 3);
+        assert!(approx_equal(distribution.stake_of(0), 0.375));
+        assert!(approx_equal(distribution.stake_of(1), 0.125));
+        assert!(approx_equal(distribution.stake_of(2), 0.5));
+        assert_eq!(distribution.node_name(1), "val-b");
+        assert_eq!(distribution.node_info(0).unwrap().operator.as_deref(), Some("op-1"));
+        assert_eq!(distribution.node_info(0).unwrap().pool, None);
+        assert_eq!(distribution.node_info(2).unwrap().pool.as_deref(), Some("pool-x"));
+
+        // Quoted fields may contain commas and (doubled) quotes.
+        let distribution = CustomStakeDistribution::from_csv("\"val, \"\"a\"\"\",\"3\",op-1\nval-b, 1 ,\"\"\n");
+        assert_eq!(distribution.node_name(0), "val, \"a\"");
+        assert_eq!(distribution.node_info(0).unwrap().operator.as_deref(), Some("op-1"));
+        assert_eq!(distribution.node_info(1).unwrap().operator, None);
+        assert!(approx_equal(distribution.stake_of(1), 0.25));
+    }
+
+    #[test]
+    #[should_panic(expected = "unterminated quoted field")]
+    fn custom_stake_distribution_csv_unterminated_quote() {
+        CustomStakeDistribution::from_csv("\"val-a,3\n");
+    }
+
+    #[test]
+    fn custom_stake_distribution_json() {
+        let distribution = CustomStakeDistribution::from_json(r#"{"validators": [
+            {"address": "val-a", "stake": "300000000000000000000000000000000", "operator": "op-1"},
+            {"address": "val-b", "stake": "100000000000000000000000000000000"},
+            {"address": "val-c", "stake": 0}
+        ]}"#);
+        assert_eq!(distribution.num_nodes(), 3);
+        assert!(approx_equal(distribution.stake_of(0), 0.75));
+        assert!(approx_equal(distribution.stake_of(1), 0.25));
+        assert_eq!(distribution.node_name(0), "val-a");
+        assert_eq!(distribution.node_info(1).unwrap().operator, None);
+        assert_eq!(distribution.stake_of(2), 0.0);
+    }
+
+    #[test]
+    fn custom_stake_distribution_exact() {
+        let distribution = CustomStakeDistribution::from_plain("1\n0\n1000000000000000000000000000000000000000\n");
+        assert!(approx_equal(distribution.stake_of(0) * 1e39, 1.0));
+        assert_eq!(distribution.node_at(&BigInt::from(0)), 0);
+        assert_eq!(distribution.node_at(&BigInt::from(1)), 2);
+        for _ in 0..1000 {
+            assert_eq!(distribution.sample(), 2);
+        }
+    }
+    #[test]
+    fn custom_stake_units_are_exact() {
+        // As floating point fractions, both stakes round to 1/2.
+        let half = BigInt::from(1u64 << 60);
+        let distribution = CustomStakeDistribution::from_stakes(vec![&half + 1, &half - 1]);
+        assert_eq!(distribution.stake_units(0, 1 << 61), (1 << 60) + 1);
+        assert_eq!(distribution.stake_units(1, 1 << 61), (1 << 60) - 1);
+        assert_eq!(distribution.stake_units(2, 1 << 61), 0);
+        // Halves are rounded up.
+        assert_eq!(distribution.stake_units(0, 1), 1);
+    }
+}
@@ -1,43 +1,324 @@
 use crate::strength::stake_distribution::*;
+use crate::strength::entity_grouping::EntityGrouping;
+use crate::strength::bribery::{min_bribe, PriceFunction};
 use itertools::Itertools;
 
+// How much each validator of a sample counts when voting.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum VoteWeight {
+    // One vote per seat, i.e., a node votes with its number of seats.
+    Seat,
+    // One vote per distinct node, regardless of its number of seats.
+    Node,
+    // Each node votes with its stake, regardless of its number of seats.
+    Stake,
+}
+
+impl VoteWeight {
+    pub fn name(&self) -> &'static str {
+        match self {
+            VoteWeight::Seat => "per seat",
+            VoteWeight::Node => "per node",
+            VoteWeight::Stake => "per stake",
+        }
+    }
+}
+
+// How the seats of a sample are assigned to nodes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Selection {
+    // Each seat goes to a node drawn independently with probability proportional to its stake.
+    Random,
+    // Same as Random, but no node holds more than the given number of seats. Draws of a node
+    // already holding that many seats are repeated.
+    SeatCap(usize),
+    // The given number of nodes with the most stake always get one seat each. The remaining seats
+    // are filled as with Random, but only from the other nodes.
+    TopKPlusRandom(usize),
+}
+
+impl Selection {
+    pub fn name(&self) -> String {
+        match self {
+            Selection::Random => "no cap".to_owned(),
+            Selection::SeatCap(max_seats) => format!("seat cap {}", max_seats),
+            Selection::TopKPlusRandom(k) => format!("top {} + random", k),
+        }
+    }
+}
+
+// Returns the k nodes with the most stake, largest first (ties broken by node id).
+pub fn top_nodes<D: StakeDistribution>(distribution: &D, k: usize) -> Vec<usize> {
+    (0..distribution.num_nodes())
+        .sorted_by(|&a, &b| distribution.stake_of(b).total_cmp(&distribution.stake_of(a)).then(a.cmp(&b)))
+        .take(k)
+        .collect()
+}
+
 // Represents a single validator sample with some given underlying stake distribution.
 pub struct ValidatorSample<'a, D: StakeDistribution> {
     distribution: &'a D,
-    nodes: Vec<usize>
+    nodes: Vec<usize>,
+    vote_weight: VoteWeight,
 }
 
 impl<'a, D: StakeDistribution> ValidatorSample<'a, D> {
     pub fn new(size: usize, distribution: &'a D) -> Self {
-        let mut nodes = vec![0; size];
-        for n in 0..size {
-            nodes[n] = distribution.sample();
+        Self::from_nodes((0..size).map(|_| distribution.sample()).collect(), distribution)
+    }
+
+    // Creates a sample of the given size using the given selection policy.
+    pub fn select(size: usize, distribution: &'a D, selection: Selection) -> Self {
+        match selection {
+            Selection::Random => Self::new(size, distribution),
+            Selection::SeatCap(max_seats) => {
+                // Nodes without stake are never drawn and cannot fill any seats.
+                let nodes_with_stake = (0..distribution.num_nodes()).filter(|&n| distribution.stake_of(n) > 0.0).count();
+                if max_seats * nodes_with_stake < size {
+                    panic!("Cannot fill {} seats with at most {} seats per node", size, max_seats);
+                }
+                let mut seats = vec![0; distribution.num_nodes()];
+                let mut nodes = Vec::with_capacity(size);
+                while nodes.len() < size {
+                    let node = distribution.sample();
+                    if seats[node] < max_seats {
+                        seats[node] += 1;
+                        nodes.push(node);
+                    }
+                }
+                Self::from_nodes(nodes, distribution)
+            }
+            Selection::TopKPlusRandom(k) => {
+                if k > size || k > distribution.num_nodes() {
+                    panic!("Cannot include the top {} nodes in a sample of size {}", k, size);
+                }
+                let mut nodes = top_nodes(distribution, k);
+                let mut is_top = vec![false; distribution.num_nodes()];
+                for &node in &nodes {
+                    is_top[node] = true;
+                }
+                // The random seats can only be filled if some node outside the top k has stake.
+                if size > k && !(0..distribution.num_nodes()).any(|n| !is_top[n] && distribution.stake_of(n) > 0.0) {
+                    panic!("Cannot fill {} random seats: no node with stake outside the top {}", size - k, k);
+                }
+                while nodes.len() < size {
+                    // Draws of top nodes are repeated, which is the same as sampling from the rest.
+                    let node = distribution.sample();
+                    if !is_top[node] {
+                        nodes.push(node);
+                    }
+                }
+                Self::from_nodes(nodes, distribution)
+            }
         }
-        nodes.sort_unstable_by(|&a, &b| distribution.stake_of(a).total_cmp(&distribution.stake_of(b)));
+    }
+
+    // Creates a sample from nodes selected by other means than ValidatorSample::new(),
+    // given as one entry per seat.
+    pub fn from_nodes(mut nodes: Vec<usize>, distribution: &'a D) -> Self {
+        // Sort by stake, keeping the seats of the same node next to each other.
+        nodes.sort_unstable_by(|&a, &b| distribution.stake_of(a).total_cmp(&distribution.stake_of(b)).then(a.cmp(&b)));
 
         Self{
             distribution,
             nodes,
+            vote_weight: VoteWeight::Seat,
         }
     }
 
+    // Sets how the validators of the sample vote (one vote per seat by default).
+    // The quorum stakes and bribes then refer to quorums of votes under this scheme.
+    pub fn with_vote_weight(mut self, vote_weight: VoteWeight) -> Self {
+        self.vote_weight = vote_weight;
+        self
+    }
+
+    // Returns the number of votes of a node with the given number of seats.
+    fn votes_of(&self, node: usize, seats: usize) -> f64 {
+        match self.vote_weight {
+            VoteWeight::Seat => seats as f64,
+            VoteWeight::Node => 1.0,
+            VoteWeight::Stake => self.distribution.stake_of(node),
+        }
+    }
+
+    // Returns the total number of votes of the nodes of the sample for which is_selected is true.
+    pub fn votes(&self, is_selected: impl Fn(usize) -> bool) -> f64 {
+        self.seats_by_node().into_iter()
+            .filter(|&(n, _)| is_selected(n))
+            .map(|(n, seats)| self.votes_of(n, seats))
+            .sum()
+    }
+
+    // Returns the total number of votes of the sample.
+    pub fn total_votes(&self) -> f64 {
+        self.votes(|_| true)
+    }
+
     pub fn total_stake(&self) -> f64 {
         self.nodes.iter().unique().map(|n| {self.distribution.stake_of(*n)}).sum()
     }
 
+    // Returns the smallest combined stake of validators holding a strong quorum of votes,
+    // taking the "poorest" validators first.
     pub fn strong_quorum_stake(&self) -> f64 {
-        self.nodes.iter()// Iterate over the validator sample sorted by stake.
-            .take(self.nodes.len() - (self.nodes.len() / 3))// Take the "poorest" two thirds
-            .unique()// Count each validator only once
-            .map(|n| {self.distribution.stake_of(*n)}) // Map validators to their stakes.
-            .sum() // Sum the stakes of all validators in the "poorest" 2/3 of the seats.
+        self.quorum_stake(self.strong_quorum_votes())
     }
 
+    // Same as strong_quorum_stake(), but for a weak quorum of votes.
     pub fn weak_quorum_stake(&self) -> f64 {
-        self.nodes.iter()// Iterate over the validator sample sorted by stake.
-            .take((self.nodes.len() + 2) / 3)// Take the "poorest" two thirds
-            .unique()// Count each validator only once
-            .map(|n| {self.distribution.stake_of(*n)}) // Map validators to their stakes.
-            .sum() // Sum the stakes of all validators in the "poorest" 2/3 of the seats.
+        self.quorum_stake(self.weak_quorum_votes())
+    }
+
+    fn quorum_stake(&self, quorum_votes: f64) -> f64 {
+        let mut votes = 0.0;
+        let mut stake = 0.0;
+        // seats_by_node() is sorted by stake, so the "poorest" validators come first.
+        for (n, seats) in self.seats_by_node() {
+            if votes >= quorum_votes {
+                break;
+            }
+            votes += self.votes_of(n, seats);
+            stake += self.distribution.stake_of(n);
+        }
+        stake
+    }
+
+    // Returns the number of votes in a strong quorum. With one vote per seat, this is
+    // strong_quorum_size(). With one vote per node, it is the same for the number of distinct nodes.
+    // With votes weighted by stake, it is 2/3 of the sample's total stake.
+    pub fn strong_quorum_votes(&self) -> f64 {
+        match self.vote_weight {
+            VoteWeight::Seat => self.strong_quorum_size() as f64,
+            VoteWeight::Node => {
+                let nodes = self.seats_by_node().len();
+                (nodes - nodes / 3) as f64
+            }
+            VoteWeight::Stake => self.total_stake() * 2.0 / 3.0,
+        }
+    }
+
+    // Same as strong_quorum_votes(), but for a weak quorum (1/3 instead of 2/3).
+    pub fn weak_quorum_votes(&self) -> f64 {
+        match self.vote_weight {
+            VoteWeight::Seat => self.weak_quorum_size() as f64,
+            VoteWeight::Node => self.seats_by_node().len().div_ceil(3) as f64,
+            VoteWeight::Stake => self.total_stake() / 3.0,
+        }
+    }
+
+    // Returns the number of seats in a strong quorum (i.e., more than 2/3 of the seats).
+    pub fn strong_quorum_size(&self) -> usize {
+        self.nodes.len() - (self.nodes.len() / 3)
+    }
+
+    // Returns the number of seats in a weak quorum (i.e., at least 1/3 of the seats).
+    pub fn weak_quorum_size(&self) -> usize {
+        self.nodes.len().div_ceil(3)
+    }
+
+    // Returns the sampled nodes (one entry per seat), sorted by stake.
+    pub fn nodes(&self) -> &[usize] {
+        &self.nodes
+    }
+
+    // Returns the distinct sampled nodes, sorted by stake, each with its number of seats.
+    pub fn seats_by_node(&self) -> Vec<(usize, usize)> {
+        self.nodes.iter().dedup_with_count().map(|(seats, &n)| (n, seats)).collect()
+    }
+
+    pub fn distribution(&self) -> &D {
+        self.distribution
+    }
+
+    // Same as strong_quorum_stake(), except that nodes run by the same entity are treated as one.
+    // An entity's stake is the combined stake of its nodes present in the sample.
+    pub fn entity_strong_quorum_stake(&self, grouping: &EntityGrouping) -> f64 {
+        self.entity_quorum_stake(grouping, self.strong_quorum_votes())
+    }
+
+    // Same as weak_quorum_stake(), except that nodes run by the same entity are treated as one.
+    pub fn entity_weak_quorum_stake(&self, grouping: &EntityGrouping) -> f64 {
+        self.entity_quorum_stake(grouping, self.weak_quorum_votes())
+    }
+
+    fn entity_quorum_stake(&self, grouping: &EntityGrouping, quorum_votes: f64) -> f64 {
+        let mut entity_stakes = vec![0.0; grouping.num_entities()];
+        let mut entity_votes = vec![0.0; grouping.num_entities()];
+        for (n, seats) in self.seats_by_node() {
+            entity_stakes[grouping.entity_of(n)] += self.distribution.stake_of(n);
+            entity_votes[grouping.entity_of(n)] += self.votes_of(n, seats);
+        }
+
+        let mut votes = 0.0;
+        let mut stake = 0.0;
+        let entities = self.nodes.iter()
+            .map(|n| grouping.entity_of(*n))
+            .unique()
+            .sorted_by(|&a, &b| entity_stakes[a].total_cmp(&entity_stakes[b]).then(a.cmp(&b)));// Sort entities by stake.
+        for e in entities {
+            if votes >= quorum_votes {
+                break;
+            }
+            votes += entity_votes[e];
+            stake += entity_stakes[e];
+        }
+        stake
+    }
+
+    // Returns the minimal total price of bribing validators that hold a strong quorum of votes.
+    // Bribes require integer votes, i.e., votes per seat or per node.
+    pub fn strong_quorum_bribe(&self, prices: &dyn PriceFunction) -> f64 {
+        min_bribe(&self.node_prices(prices), self.strong_quorum_votes() as usize)
+    }
+
+    // Returns the minimal total price of bribing validators that hold a weak quorum of votes.
+    pub fn weak_quorum_bribe(&self, prices: &dyn PriceFunction) -> f64 {
+        min_bribe(&self.node_prices(prices), self.weak_quorum_votes() as usize)
+    }
+
+    fn node_prices(&self, prices: &dyn PriceFunction) -> Vec<(f64, usize)> {
+        if self.vote_weight == VoteWeight::Stake {
+            panic!("Bribes are not supported for votes weighted by stake");
+        }
+        self.seats_by_node().into_iter()
+            .map(|(n, seats)| (prices.price(n, self.distribution.stake_of(n)), self.votes_of(n, seats) as usize))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quorum_stakes_per_vote_weight() {
+        // Node 0 (stake 0.1) has 4 seats, node 1 (stake 0.2) has 1, node 2 (stake 0.7) has 1.
+        let distribution = CustomStakeDistribution::from_plain("1\n2\n7\n");
+        let sample = ValidatorSample::from_nodes(vec![0, 0, 0, 0, 1, 2], &distribution);
+        assert!((sample.strong_quorum_stake() - 0.1).abs() < 1e-12);
+        let sample = sample.with_vote_weight(VoteWeight::Node);
+        assert!((sample.strong_quorum_stake() - 0.3).abs() < 1e-12);
+        let sample = sample.with_vote_weight(VoteWeight::Stake);
+        assert!((sample.strong_quorum_stake() - 1.0).abs() < 1e-12);
+        assert!((sample.weak_quorum_stake() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn seat_cap() {
+        let distribution = CustomStakeDistribution::from_plain("1\n1\n98\n");
+        let sample = ValidatorSample::select(5, &distribution, Selection::SeatCap(2));
+        assert!(sample.seats_by_node().iter().all(|&(_, seats)| seats <= 2));
+        assert_eq!(sample.nodes().len(), 5);
+    }
+
+    #[test]
+    fn top_k_plus_random() {
+        let distribution = CustomStakeDistribution::from_plain("1\n5\n3\n1\n");
+        assert_eq!(top_nodes(&distribution, 2), vec![1, 2]);
+        let sample = ValidatorSample::select(10, &distribution, Selection::TopKPlusRandom(2));
+        let seats = sample.seats_by_node();
+        assert!(seats.contains(&(1, 1)) && seats.contains(&(2, 1)));
+        assert_eq!(seats.iter().map(|&(_, s)| s).sum::<usize>(), 10);
     }
 }
@@ -0,0 +1,198 @@
+use std::fmt::Write;
+use std::fs;
+use rand_distr::num_traits::ToPrimitive;
+use crate::representativeness::{failure_probability, simulate_prob};
+use crate::representativeness::weighted_voting::simulate_vote_weight_prob;
+use crate::strength::simulation::Simulation;
+use crate::strength::stake_distribution::*;
+use crate::strength::validator_sample::{Selection, VoteWeight};
+use crate::table::Table;
+
+// A Sweep evaluates every combination of sample size, fraction of faulty stake and fault threshold
+// on stake distributions. For each combination, it computes the exact failure probability
+// (each seat faulty independently, see failure_probability()), a simulated estimate of the same
+// probability (see simulate_prob()), a simulated failure probability on the given distribution
+// if each node is faulty independently (see simulate_vote_weight_prob()) and the average weak and
+// strong quorum stakes of samples of that size.
+// Fractions are given as (numerator, denominator).
+pub struct Sweep {
+    pub sample_sizes: Vec<u32>,
+    pub faulty_fractions: Vec<(u32, u32)>,
+    pub fault_thresholds: Vec<(u32, u32)>,
+    pub sim_iterations: usize,
+    pub strength_iterations: usize,
+}
+
+// One cell of the grid computed by a Sweep.
+pub struct SweepRow {
+    distribution: String,
+    sample_size: u32,
+    faulty_fraction: (u32, u32),
+    fault_threshold: (u32, u32),
+    exact: f64,
+    simulated: f64,
+    simulated_per_node: f64,
+    weak_quorum_stake: f64,
+    strong_quorum_stake: f64,
+}
+
+impl Sweep {
+    // Returns the rows of the grid for the given distribution, labeled with the given name.
+    pub fn run<D: StakeDistribution>(&self, name: &str, distribution: &D) -> Vec<SweepRow> {
+        let mut rows = vec![];
+        for &n in &self.sample_sizes {
+            // Quorum stakes only depend on the sample size.
+            let mut simulation = Simulation::new(distribution);
+            simulation.run(n as usize, self.strength_iterations);
+            for &(f_num, f_denom) in &self.faulty_fractions {
+                for &(thr_num, thr_denom) in &self.fault_thresholds {
+                    let threshold = thr_num as f64 / thr_denom as f64;
+                    rows.push(SweepRow {
+                        distribution: name.to_owned(),
+                        sample_size: n,
+                        faulty_fraction: (f_num, f_denom),
+                        fault_threshold: (thr_num, thr_denom),
+                        exact: failure_probability(n, f_num, f_denom, threshold).to_f64().unwrap(),
+                        simulated: simulate_prob(n as u64, f_num as f64 / f_denom as f64, threshold, self.sim_iterations),
+                        simulated_per_node: simulate_vote_weight_prob(
+                            distribution,
+                            n as usize,
+                            Selection::Random,
+                            f_num as f64 / f_denom as f64,
+                            VoteWeight::Seat,
+                            threshold,
+                            self.sim_iterations,
+                        ),
+                        weak_quorum_stake: simulation.weak_quorum_stake().0,
+                        strong_quorum_stake: simulation.strong_quorum_stake().0,
+                    });
+                }
+            }
+        }
+
+        rows
+    }
+}
+
+const COLUMNS: [&str; 9] = [
+    "distribution", "n", "f", "threshold", "exact", "simulated", "simulated per node", "weak quorum stake",
+    "strong quorum stake",
+];
+
+// Quotes a CSV field if it contains a comma, quote or line break (doubling the quotes inside).
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+// Renders the rows as CSV with a header line. Fractions are written as decimal numbers.
+pub fn to_csv(rows: &[SweepRow]) -> String {
+    let mut csv = COLUMNS.iter().map(|c| c.replace(' ', "_")).collect::<Vec<_>>().join(",");
+    csv.push('\n');
+    for row in rows {
+        writeln!(csv, "{},{},{},{},{},{},{},{},{}",
+                 csv_field(&row.distribution),
+                 row.sample_size,
+                 row.faulty_fraction.0 as f64 / row.faulty_fraction.1 as f64,
+                 row.fault_threshold.0 as f64 / row.fault_threshold.1 as f64,
+                 row.exact,
+                 row.simulated,
+                 row.simulated_per_node,
+                 row.weak_quorum_stake,
+                 row.strong_quorum_stake,
+        ).unwrap();
+    }
+
+    csv
+}
+
+// Renders the rows as a Markdown table.
+pub fn to_markdown(rows: &[SweepRow]) -> String {
+    let mut table = Table::new(&COLUMNS).with_left_aligned(0);
+    for row in rows {
+        table.add_row(vec![
+            row.distribution.clone(),
+            row.sample_size.to_string(),
+            format!("{}/{}", row.faulty_fraction.0, row.faulty_fraction.1),
+            format!("{}/{}", row.fault_threshold.0, row.fault_threshold.1),
+            format!("{:.2e}", row.exact),
+            format!("{:.2e}", row.simulated),
+            format!("{:.2e}", row.simulated_per_node),
+            format!("{:.4}", row.weak_quorum_stake),
+            format!("{:.4}", row.strong_quorum_stake),
+        ]);
+    }
+    table.to_markdown()
+}
+
+pub fn sweep() {
+    let num_nodes = 296;
+    let exp_distribution_ps = [0.05, 0.1, 0.2];
+    let actual_distribution_file = "node-stakes-september-2025";
+    let output_directory = "output";
+    let sweep = Sweep {
+        sample_sizes: vec![50, 90, 111, 141, 200],
+        faulty_fractions: vec![(1, 5), (1, 4), (1, 3)],
+        fault_thresholds: vec![(1, 3), (2, 3)],
+        sim_iterations: 2000,
+        strength_iterations: 1000,
+    };
+
+    println!();
+    println!("================================================================================");
+    println!("PARAMETER SWEEP");
+    println!("I.e.: How do failure probabilities and quorum stakes depend on the parameters?");
+    println!("================================================================================");
+    println!();
+
+    let mut rows = sweep.run(&format!("uniform ({} nodes)", num_nodes), &UniformStakeDistribution::new(num_nodes));
+    for p in exp_distribution_ps {
+        rows.extend(sweep.run(&format!("exponential (p = {})", p), &ExponentialStakeDistribution::new(num_nodes, p)));
+    }
+    rows.extend(sweep.run(actual_distribution_file, &CustomStakeDistribution::from_file(actual_distribution_file)));
+
+    fs::create_dir_all(output_directory).unwrap();
+    let csv_file = format!("{}/sweep.csv", output_directory);
+    let markdown_file = format!("{}/sweep.md", output_directory);
+    fs::write(&csv_file, to_csv(&rows)).unwrap();
+    fs::write(&markdown_file, to_markdown(&rows)).unwrap();
+    println!("Sample sizes: {:?}", sweep.sample_sizes);
+    println!("Fractions of faulty stake: {:?}", sweep.faulty_fractions);
+    println!("Fault thresholds: {:?}", sweep.fault_thresholds);
+    println!("Distributions: uniform, exponential with p in {:?}, actual (file '{}')", exp_distribution_ps, actual_distribution_file);
+    println!("exact: failure probability if each seat is faulty independently");
+    println!("simulated: simulation of the exact failure probability ({} iterations)", sweep.sim_iterations);
+    println!("simulated per node: failure probability on the distribution if each node is faulty independently ({} iterations)", sweep.sim_iterations);
+    println!("weak / strong quorum stake: averages over {} samples", sweep.strength_iterations);
+    println!("Wrote {} rows to '{}' and '{}'.", rows.len(), csv_file, markdown_file);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sweep_grid() {
+        let sweep = Sweep {
+            sample_sizes: vec![3, 4],
+            faulty_fractions: vec![(1, 2)],
+            fault_thresholds: vec![(1, 3), (2, 3)],
+            sim_iterations: 1000,
+            strength_iterations: 10,
+        };
+        let rows = sweep.run("uniform", &UniformStakeDistribution::new(10));
+        assert_eq!(rows.len(), 4);
+        // 3 seats, at least 2 of which faulty with probability 1/2.
+        assert!((rows[1].exact - 0.5).abs() < 1e-12);
+        assert!((rows[1].simulated - 0.5).abs() < 0.1);
+        let csv = to_csv(&rows);
+        assert_eq!(csv.lines().count(), 5);
+        assert!(csv.starts_with("distribution,n,f,threshold,exact,simulated,simulated_per_node,weak_quorum_stake,strong_quorum_stake\n"));
+        assert_eq!(to_markdown(&rows).lines().count(), 6);
+        assert_eq!(csv_field("exponential (p = 0.1)"), "exponential (p = 0.1)");
+        assert_eq!(csv_field("stakes, \"final\""), "\"stakes, \"\"final\"\"\"");
+    }
+}
@@ -0,0 +1,164 @@
+use std::fmt::Write;
+use crate::plot::escape;
+
+// Table holds rows of already formatted cells under a header and renders them for the terminal
+// (with the box drawing used throughout the console output), as Markdown, as LaTeX (booktabs) or
+// as HTML. Columns are right-aligned unless set to be left-aligned.
+pub struct Table {
+    header: Vec<String>,
+    rows: Vec<Vec<String>>,
+    left_aligned: Vec<bool>,
+}
+
+impl Table {
+    pub fn new(header: &[&str]) -> Self {
+        Self {
+            header: header.iter().map(|h| h.to_string()).collect(),
+            rows: vec![],
+            left_aligned: vec![false; header.len()],
+        }
+    }
+
+    // Makes the given column (numbering starting from zero) left-aligned, e.g., for names.
+    pub fn with_left_aligned(mut self, column: usize) -> Self {
+        self.left_aligned[column] = true;
+        self
+    }
+
+    pub fn add_row(&mut self, row: Vec<String>) {
+        if row.len() != self.header.len() {
+            panic!("Table row has {} cells, but the header has {}", row.len(), self.header.len());
+        }
+        self.rows.push(row);
+    }
+
+    // Returns the width of each column, i.e., the length of its longest cell (including the header).
+    fn column_widths(&self) -> Vec<usize> {
+        (0..self.header.len())
+            .map(|i| self.rows.iter().map(|r| &r[i]).chain([&self.header[i]]).map(|c| c.chars().count()).max().unwrap())
+            .collect()
+    }
+
+    pub fn to_terminal(&self) -> String {
+        let widths = self.column_widths();
+        let line = |cells: &[String]| {
+            let cells: Vec<String> = cells.iter().zip(&widths).zip(&self.left_aligned)
+                .map(|((c, &w), &left)| if left { format!("{:<w$}", c, w = w) } else { format!("{:>w$}", c, w = w) })
+                .collect();
+            format!("| {} |\n", cells.join(" | "))
+        };
+        // The borders span the row without its leading and trailing "|".
+        let border_width = widths.iter().sum::<usize>() + 3 * widths.len() - 1;
+
+        let mut text = format!(" {} \n", "_".repeat(border_width));
+        text.push_str(&line(&self.header));
+        writeln!(text, " {} ", "-".repeat(border_width)).unwrap();
+        for row in &self.rows {
+            text.push_str(&line(row));
+        }
+        writeln!(text, " {} ", "-".repeat(border_width)).unwrap();
+        text
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let line = |cells: &[String]| {
+            format!("| {} |\n", cells.iter().map(|c| c.replace('|', "\\|")).collect::<Vec<_>>().join(" | "))
+        };
+        let mut markdown = line(&self.header);
+        let alignments: String = self.left_aligned.iter().map(|&left| if left { ":---|" } else { "---:|" }).collect();
+        writeln!(markdown, "|{}", alignments).unwrap();
+        for row in &self.rows {
+            markdown.push_str(&line(row));
+        }
+        markdown
+    }
+
+    // Renders a tabular environment using the booktabs package (\toprule, \midrule, \bottomrule).
+    pub fn to_latex(&self) -> String {
+        let line = |cells: &[String]| {
+            format!("{} \\\\\n", cells.iter().map(|c| latex_escape(c)).collect::<Vec<_>>().join(" & "))
+        };
+        let alignments: String = self.left_aligned.iter().map(|&left| if left { 'l' } else { 'r' }).collect();
+        let mut latex = format!("\\begin{{tabular}}{{{}}}\n\\toprule\n", alignments);
+        latex.push_str(&line(&self.header));
+        latex.push_str("\\midrule\n");
+        for row in &self.rows {
+            latex.push_str(&line(row));
+        }
+        latex.push_str("\\bottomrule\n\\end{tabular}\n");
+        latex
+    }
+
+    // Renders an HTML table. Cells of left-aligned columns have the class "left", to be styled by
+    // the enclosing document.
+    pub fn to_html(&self) -> String {
+        let line = |tag: &str, cells: &[String]| {
+            let cells: String = cells.iter().zip(&self.left_aligned)
+                .map(|(c, &left)| format!("<{0}{1}>{2}</{0}>", tag, if left { " class=\"left\"" } else { "" }, escape(c)))
+                .collect();
+            format!("<tr>{}</tr>\n", cells)
+        };
+        let mut html = String::from("<table>\n");
+        html.push_str(&line("th", &self.header));
+        for row in &self.rows {
+            html.push_str(&line("td", row));
+        }
+        html.push_str("</table>\n");
+        html
+    }
+}
+
+fn latex_escape(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        match c {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_table() {
+        let mut table = Table::new(&["n", "not safe"]);
+        table.add_row(vec!["141".to_owned(), "1.00e-13".to_owned()]);
+        assert_eq!(table.to_terminal(), concat!(
+            " ________________ \n",
+            "|   n | not safe |\n",
+            " ---------------- \n",
+            "| 141 | 1.00e-13 |\n",
+            " ---------------- \n",
+        ));
+        assert_eq!(table.to_markdown(), "| n | not safe |\n|---:|---:|\n| 141 | 1.00e-13 |\n");
+        assert_eq!(table.to_latex(), concat!(
+            "\\begin{tabular}{rr}\n\\toprule\n",
+            "n & not safe \\\\\n\\midrule\n",
+            "141 & 1.00e-13 \\\\\n\\bottomrule\n\\end{tabular}\n",
+        ));
+        let mut table = Table::new(&["a_b"]);
+        table.add_row(vec!["50%".to_owned()]);
+        assert!(table.to_latex().contains("a\\_b \\\\\n\\midrule\n50\\% \\\\"));
+        let mut table = Table::new(&["name", "n"]).with_left_aligned(0);
+        table.add_row(vec!["a|b".to_owned(), "1".to_owned()]);
+        assert_eq!(table.to_terminal().lines().nth(3), Some("| a|b  | 1 |"));
+        assert_eq!(table.to_markdown(), "| name | n |\n|:---|---:|\n| a\\|b | 1 |\n");
+        assert!(table.to_latex().starts_with("\\begin{tabular}{lr}"));
+        let mut table = Table::new(&["a<b", "n"]).with_left_aligned(0);
+        table.add_row(vec!["x&y".to_owned(), "1".to_owned()]);
+        assert_eq!(table.to_html(), concat!(
+            "<table>\n<tr><th class=\"left\">a&lt;b</th><th>n</th></tr>\n",
+            "<tr><td class=\"left\">x&amp;y</td><td>1</td></tr>\n</table>\n",
+        ));
+    }
+}
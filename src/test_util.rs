@@ -0,0 +1,11 @@
+// Helpers shared by the tests of several modules.
+use num_bigint::BigInt;
+use num_rational::BigRational;
+
+pub fn approx_equal(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-10
+}
+
+pub fn rational(num: i32, denom: i32) -> BigRational {
+    BigRational::new(BigInt::from(num), BigInt::from(denom))
+}
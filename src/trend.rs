@@ -0,0 +1,189 @@
+use std::fs;
+use crate::representativeness::simulate_entity_prob;
+use crate::strength::concentration::*;
+use crate::strength::entity_grouping::EntityGrouping;
+use crate::strength::simulation::Simulation;
+use crate::strength::stake_distribution::{CustomStakeDistribution, StakeDistribution};
+use crate::strength::validator_sample::ValidatorSample;
+use crate::table::Table;
+
+const MONTHS: [&str; 12] = [
+    "january", "february", "march", "april", "may", "june",
+    "july", "august", "september", "october", "november", "december",
+];
+
+// A stake distribution loaded from a dated stake file.
+// The date is (year, month, day), with day 0 if the file name only specifies a month.
+pub struct StakeSnapshot {
+    pub date: (u32, u32, u32),
+    pub file_name: String,
+    pub distribution: CustomStakeDistribution,
+}
+
+impl StakeSnapshot {
+    pub fn date_string(&self) -> String {
+        let (year, month, day) = self.date;
+        if day == 0 {
+            format!("{:04}-{:02}", year, month)
+        } else {
+            format!("{:04}-{:02}-{:02}", year, month, day)
+        }
+    }
+}
+
+// Extracts a date from a stake file name. Supported are ISO dates (e.g. "stakes-2025-09-30.csv"),
+// ISO months (e.g. "stakes-2025-09") and month names followed by the year
+// (e.g. "node-stakes-september-2025"). Returns None if the name contains no date.
+pub fn parse_snapshot_date(file_name: &str) -> Option<(u32, u32, u32)> {
+    let stem = file_name.split('.').next().unwrap_or_default().to_lowercase();
+    let parts: Vec<&str> = stem.split(['-', '_']).collect();
+    let number = |part: &str, digits: usize| -> Option<u32> {
+        (part.len() == digits && part.chars().all(|c| c.is_ascii_digit())).then(|| part.parse().unwrap())
+    };
+
+    for (i, part) in parts.iter().enumerate() {
+        let next = parts.get(i + 1).copied().unwrap_or_default();
+        if let Some(month) = MONTHS.iter().position(|m| m == part) {
+            if let Some(year) = number(next, 4) {
+                return Some((year, month as u32 + 1, 0));
+            }
+        } else if let (Some(year), Some(month)) = (number(part, 4), number(next, 2)) {
+            if !(1..=12).contains(&month) {
+                continue;
+            }
+            let day = parts.get(i + 2).and_then(|d| number(d, 2)).filter(|d| (1..=31).contains(d));
+            return Some((year, month, day.unwrap_or(0)));
+        }
+    }
+
+    None
+}
+
+// Loads all stake files in the given directory whose names start with the given prefix and
+// contain a date (see parse_snapshot_date()). The snapshots are ordered by date.
+pub fn load_snapshots(directory: &str, prefix: &str) -> Vec<StakeSnapshot> {
+    let mut snapshots: Vec<StakeSnapshot> = fs::read_dir(directory).unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.is_file())
+        .filter_map(|path| {
+            let file_name = path.file_name()?.to_str()?.to_owned();
+            let date = parse_snapshot_date(&file_name).filter(|_| file_name.starts_with(prefix))?;
+            Some(StakeSnapshot {
+                date,
+                distribution: CustomStakeDistribution::from_file(path.to_str()?),
+                file_name,
+            })
+        })
+        .collect();
+    snapshots.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.file_name.cmp(&b.file_name)));
+
+    snapshots
+}
+
+// Returns the smallest sample size up to max_sample_size for which the strong quorum stake,
+// averaged over the given number of samples, reaches target_stake, together with the standard
+// error of that average. Returns None if even max_sample_size does not reach the target.
+// The samples of all sizes are prefixes of the same random seat sequences (one per iteration), and
+// the sizes are scanned in increasing order. Thus, the result does not rely on the (noisy) average
+// growing with the sample size, and the noise affects all sizes alike instead of independently.
+// The average at the returned size is accurate to about twice the returned standard error.
+pub fn min_sample_size_for_stake<D: StakeDistribution>(
+    distribution: &D,
+    target_stake: f64,
+    max_sample_size: usize,
+    iterations: usize,
+) -> Option<(usize, f64)> {
+    let seats: Vec<Vec<usize>> = (0..iterations)
+        .map(|_| (0..max_sample_size).map(|_| distribution.sample()).collect())
+        .collect();
+    for sample_size in 1..=max_sample_size {
+        let stakes: Vec<f64> = seats.iter()
+            .map(|nodes| ValidatorSample::from_nodes(nodes[..sample_size].to_vec(), distribution).strong_quorum_stake())
+            .collect();
+        let mean = stakes.iter().sum::<f64>() / iterations as f64;
+        if mean >= target_stake {
+            let variance = stakes.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (iterations - 1).max(1) as f64;
+            return Some((sample_size, (variance / iterations as f64).sqrt()));
+        }
+    }
+
+    None
+}
+
+pub fn analyze() {
+    let snapshot_directory = ".";
+    let snapshot_prefix = "node-stakes-";
+    let sample_size = 111;
+    let iterations = 1000;
+    let target_quorum_stake = 1.0 / 3.0;
+    let search_iterations = 1000;
+    let max_sample_size = 300;
+    let (f_num, f_denom) = (1, 5);
+    let correlated_sim_iterations = 2000;
+
+    println!();
+    println!("================================================================================");
+    println!("STAKE DISTRIBUTION TRENDS");
+    println!("I.e.: Is the stake becoming more or less concentrated over time?");
+    println!("================================================================================");
+    println!();
+
+    let snapshots = load_snapshots(snapshot_directory, snapshot_prefix);
+    println!("Stake snapshots: {} dated files '{}*' in directory '{}'", snapshots.len(), snapshot_prefix, snapshot_directory);
+    println!("nodes: number of nodes in the snapshot");
+    println!("gini: Gini coefficient of the stake");
+    println!("hhi: Herfindahl-Hirschman index of the stake");
+    println!("nak: Nakamoto coefficient (smallest number of nodes holding more than 1/3 of the stake)");
+    println!("weak q / strong q: average weak / strong quorum stake of a sample of size {} ({} iterations)", sample_size, iterations);
+    println!("min n: smallest sample size with average strong quorum stake at least {:.3}, scanning all sizes", target_quorum_stake);
+    println!("       on the same {} random seat sequences (see min_sample_size_for_stake())", search_iterations);
+    println!("se: standard error of that average (the average is accurate to about 2 se)");
+    println!("not live: probability of a sample of size {} not being live if each node is faulty", sample_size);
+    println!("          independently with probability {}/{} ({} iterations)", f_num, f_denom, correlated_sim_iterations);
+    println!();
+    let mut table = Table::new(&["date", "nodes", "gini", "hhi", "nak", "weak q", "strong q", "min n", "se", "not live"]);
+    for snapshot in &snapshots {
+        let distribution = &snapshot.distribution;
+        let mut simulation = Simulation::new(distribution);
+        simulation.run(sample_size, iterations);
+        let (min_sample_size, standard_error) = match min_sample_size_for_stake(distribution, target_quorum_stake, max_sample_size, search_iterations) {
+            Some((n, standard_error)) => (n.to_string(), format!("{:.4}", standard_error)),
+            None => (format!(">{}", max_sample_size), "-".to_owned()),
+        };
+        let not_live = simulate_entity_prob(
+            distribution,
+            &EntityGrouping::per_node(distribution),
+            sample_size,
+            f_num as f64 / f_denom as f64,
+            1.0/3.0,
+            correlated_sim_iterations,
+        );
+        table.add_row(vec![
+            snapshot.date_string(),
+            distribution.num_nodes().to_string(),
+            format!("{:.4}", gini_coefficient(distribution)),
+            format!("{:.4}", herfindahl_hirschman_index(distribution)),
+            nakamoto_coefficient(distribution, 1.0/3.0).to_string(),
+            format!("{:.4}", simulation.weak_quorum_stake().0),
+            format!("{:.4}", simulation.strong_quorum_stake().0),
+            min_sample_size,
+            standard_error,
+            format!("{:.2e}", not_live),
+        ]);
+    }
+    print!("{}", table.to_terminal());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_dates() {
+        assert_eq!(parse_snapshot_date("node-stakes-september-2025"), Some((2025, 9, 0)));
+        assert_eq!(parse_snapshot_date("node-stakes-2025-10.csv"), Some((2025, 10, 0)));
+        assert_eq!(parse_snapshot_date("stakes_2024-01-31.json"), Some((2024, 1, 31)));
+        assert_eq!(parse_snapshot_date("node-stakes"), None);
+        assert_eq!(parse_snapshot_date("node-stakes-2025-13"), None);
+    }
+}